@@ -1,721 +1,9759 @@
-use clap::{Parser, Subcommand};
+use base64::Engine;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use colored::{Color, Colorize};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use serde_json;
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::Path;
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{PoisonError, RwLock};
+use std::time::Duration;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use wait_timeout::ChildExt;
 
-/// Returns the path to the configuration file for storing shortcuts.
+/// Whether shortcut names are compared with exact byte equality.
 ///
-/// This function constructs the file path for the configuration file by using the user's home directory
-/// and appending the filename `.projexts_config.json` to it. It leverages the `dirs` crate to determine
-/// the home directory.
+/// Set once at startup from the global `--case-sensitive` flag. By default names are compared
+/// case-insensitively so `MyProject` and `myproject` refer to the same shortcut.
+static CASE_SENSITIVE: AtomicBool = AtomicBool::new(false);
+
+/// Whether colored output was turned off with the global `--no-color` flag.
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Whether informational messages were turned off with the global `--quiet` flag.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Prints an informational message to stdout like `println!`, unless `--quiet` was passed.
 ///
-/// # Panics
-/// This function will panic if the `dirs::home_dir()` function returns `None`, indicating that the home
-/// directory could not be determined (e.g., in environments without a user home directory, such as some
-/// containerized or certain restricted systems).
-fn config_file_path() -> PathBuf {
-    dirs::home_dir().unwrap().join(".projexts_config.json")
+/// Use this for status messages such as "Shortcut added", and `println!` for output the user asked
+/// for, such as a list of shortcuts, so `--quiet` never hides results. Errors and warnings go to
+/// stderr and are never silenced.
+macro_rules! log {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
 }
 
-/// Resets the shortcuts by removing the configuration file.
-///
-/// This function deletes the configuration file associated with the shortcuts,
-/// effectively resetting all the stored shortcuts. It does so by calling the
-/// `config_file_path()` function to get the path of the configuration file and
-/// then removing that file from the filesystem.
-///
-/// # Returns
-/// - `Ok(())` if the file is successfully removed.
-/// - `Err(io::Error)` if there is an error removing the file (e.g., file not found or permission issues).
+/// Whether machine-readable output was requested with the global `--porcelain` flag.
+static PORCELAIN: AtomicBool = AtomicBool::new(false);
+
+/// Returns whether output should be in the machine-readable `--porcelain` format.
 ///
-/// # Errors
-/// This function will return an `io::Result` which may contain an error if there are issues
-/// with file removal, such as the file not existing or lacking the necessary permissions.
-fn reset_shortcuts() -> io::Result<()> {
-    let path = config_file_path();
-    fs::remove_file(path)?;
-    Ok(())
+/// In this format, informational messages are left out and results are written as tab-separated
+/// fields, so scripts don't have to parse the human-readable text.
+fn porcelain() -> bool {
+    PORCELAIN.load(Ordering::Relaxed)
 }
 
-/// Loads the list of shortcuts from the persistent storage file.
-///
-/// This function checks if the configuration file exists at the specified path. If the file does not
-/// exist, it creates a new file with an empty JSON array (`[]`) as its content. After ensuring the
-/// file exists, it reads the data from the file, deserializes it into a `Vec<Shortcut>`, and returns
-/// the list of shortcuts.
+/// Reports the outcome of a command that changes the shortcut `name`.
 ///
-/// # Errors
-/// This function may return an error if:
-/// - The configuration file cannot be read (e.g., due to I/O errors).
-/// - The file content cannot be successfully deserialized into a `Vec<Shortcut>`.
-/// - There is an error while creating the file if it doesn't exist.
-fn load_shortcuts() -> io::Result<Vec<Shortcut>> {
-    let path = config_file_path();
-    if !path.exists() {
-        println!("Creating storage for shortcuts...");
-        // Create an empty file if it doesn't exist
-        fs::File::create(&path)?.write_all(b"[]")?;
+/// With `--porcelain`, prints `ok<TAB><name>` or `error<TAB><message>` to stdout. Otherwise only
+/// failures are reported, on stderr, prefixed with `context` (e.g., "Failed to add shortcut").
+/// Either way, a failure exits the process with status 1.
+fn report_change(name: &str, result: io::Result<()>, context: &str) {
+    match result {
+        Ok(()) if porcelain() => println!("ok\t{}", name),
+        Ok(()) => {}
+        Err(e) => {
+            if porcelain() {
+                println!("error\t{}", porcelain_field(&e.to_string()));
+            } else {
+                eprintln!("{}", failure(&format!("{}: {}", context, e)));
+            }
+            std::process::exit(1);
+        }
     }
-    let data = fs::read_to_string(path)?;
-    Ok(serde_json::from_str(&data)?)
 }
 
-/// Saves the given list of shortcuts to persistent storage.
-///
-/// This function serializes the provided vector of `Shortcut` objects into a JSON
-/// format and writes it to the configuration file. If the operation is successful,
-/// the updated list of shortcuts will be stored and available for future access.
-///
-/// # Parameters
-/// - `shortcuts`: A slice of `Shortcut` objects that represents the list of shortcuts
-///   to be saved. Each `Shortcut` contains a project name and the associated run command.
-///
-/// # Errors
-/// This function will return an error if:
-/// - The `serde_json::to_string_pretty` function fails to serialize the `shortcuts` vector.
-/// - The `fs::write` function fails to write the serialized data to the storage file.
-fn save_shortcuts(shortcuts: &[Shortcut]) -> io::Result<()> {
-    let data = serde_json::to_string_pretty(shortcuts)?;
-    fs::write(config_file_path(), data)?;
-    Ok(())
+/// Replaces tabs and line breaks in a `--porcelain` field with spaces, so every record stays on
+/// one line with a fixed number of fields.
+fn porcelain_field(text: &str) -> String {
+    text.replace(['\t', '\r', '\n'], " ")
 }
 
-/// Adds a new shortcut with the given name and command to the storage.
-///
-/// This function adds a new shortcut, consisting of a project name and a command, to the list of stored
-/// shortcuts. It first validates that the command is not empty and then ensures that all paths in the
-/// command are either absolute or can be converted to absolute paths. If any relative paths are provided,
-/// they are converted to absolute paths using `fs::canonicalize()`. If a valid path is not found for any
-/// command component, an error is returned.
+/// Whether debug messages were turned on with the global `--debug` flag.
+static DEBUG: AtomicBool = AtomicBool::new(false);
+
+/// Prints a debug message to stderr, prefixed with `[debug]`, if `--debug` was passed.
 ///
-/// # Arguments
-/// * `name` - The name of the project or shortcut.
-/// * `command` - A vector of strings representing the command to run, where each string is a part of the command (e.g., executable name, arguments).
+/// Takes the same arguments as `eprintln!`. Debug messages show what projexts is doing internally,
+/// e.g. which configuration file it reads and the shortcut it is about to run.
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        if DEBUG.load(Ordering::Relaxed) {
+            eprintln!("[debug] {}", format!($($arg)*));
+        }
+    };
+}
+
+/// Returns whether output to a stream should be colored.
 ///
-/// # Returns
-/// * `Ok(())` if the shortcut is successfully added to the storage.
-/// * `Err(io::Error)` if the command is empty, or if no valid paths are found in the command.
-fn add_shortcut(name: &str, command: Vec<String>) -> io::Result<()> {
-    if command.is_empty() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "Command cannot be empty",
-        ));
+/// Color is used only when the stream is a terminal, the `--no-color` flag was not passed, the
+/// `NO_COLOR` environment variable is unset or empty (see <https://no-color.org>), and `CLICOLOR`
+/// is not set to `0`.
+fn color_enabled(stream_is_terminal: bool) -> bool {
+    if NO_COLOR.load(Ordering::Relaxed) || !stream_is_terminal {
+        return false;
     }
+    if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+    std::env::var("CLICOLOR").map_or(true, |value| value != "0")
+}
 
-    // Convert relative paths to absolute paths where possible
-    let absolute_command: Vec<String> = command
-        .into_iter()
-        .map(|cmd| {
-            let path = Path::new(&cmd);
-            if path.is_absolute() {
-                cmd
-            } else if let Ok(abs_path) = fs::canonicalize(path) {
-                abs_path.to_string_lossy().to_string()
-            } else {
-                cmd
-            }
-        })
-        .collect();
+/// Colors `text` if the stream it is written to (stderr or stdout) should be colored.
+fn paint(text: &str, color: Color, to_stderr: bool) -> String {
+    let is_terminal = if to_stderr {
+        io::stderr().is_terminal()
+    } else {
+        io::stdout().is_terminal()
+    };
+    if color_enabled(is_terminal) {
+        text.color(color).to_string()
+    } else {
+        text.to_string()
+    }
+}
 
-    let mut shortcuts = load_shortcuts()?;
-    shortcuts.push(Shortcut {
-        project_name: name.to_string(),
-        run_command: absolute_command,
-    });
-    save_shortcuts(&shortcuts)
+/// Styles a success message printed to stdout (green).
+fn success(text: &str) -> String {
+    paint(text, Color::Green, false)
 }
 
-/// Removes a shortcut with the given name from the storage.
-///
-/// This function searches for a shortcut with the specified `name` and removes it from the list of stored
-/// shortcuts. If no shortcut with the given name is found, a message is printed indicating that the shortcut
-/// does not exist. If the shortcut is successfully removed, the list of shortcuts is saved back to storage.
-///
-/// # Arguments
-/// * `name` - The name of the project or shortcut to remove.
-///
-/// # Returns
-/// * `Ok(())` if the shortcut is removed successfully or if no matching shortcut is found (in which case no changes are made).
-/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts.
-fn remove_shortcut(name: &str) -> io::Result<()> {
-    let mut shortcuts = load_shortcuts()?;
-    let initial_len = shortcuts.len();
+/// Styles an error message printed to stderr (red).
+fn failure(text: &str) -> String {
+    paint(text, Color::Red, true)
+}
 
-    // Retain only shortcuts that do not match the given name
-    shortcuts.retain(|shortcut| shortcut.project_name != name);
+/// Styles a warning printed to stderr (yellow).
+fn warning(text: &str) -> String {
+    paint(text, Color::Yellow, true)
+}
 
-    if shortcuts.len() == initial_len {
-        println!("No shortcut found with name '{}'.", name);
+/// Styles a shortcut name printed to stdout (cyan).
+fn highlight(text: &str) -> String {
+    paint(text, Color::Cyan, false)
+}
+
+/// Returns `true` if the stored shortcut name `stored` matches the user-supplied `query`.
+///
+/// The comparison ignores ASCII case unless the global `--case-sensitive` flag was passed, in which
+/// case the names must be exactly equal. Every lookup of a shortcut by name goes through this function
+/// so all commands agree on which shortcut a name refers to.
+fn names_match(stored: &str, query: &str) -> bool {
+    if CASE_SENSITIVE.load(Ordering::Relaxed) {
+        stored == query
     } else {
-        println!("Shortcut '{}' removed successfully.", name);
-        save_shortcuts(&shortcuts)?;
+        stored.eq_ignore_ascii_case(query)
     }
-    Ok(())
 }
 
-/// Lists all the stored shortcuts and their associated commands.
+/// The longest shortcut name `validate_shortcut_name` accepts, in characters.
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Checks that `name` can be used as a shortcut name.
 ///
-/// This function loads the list of shortcuts from storage and prints each shortcut's project name
-/// along with the corresponding run command. If no shortcuts are found, a message indicating that
-/// no shortcuts are available is printed.
+/// A name must be non-empty, at most 64 characters long, made up only of ASCII letters, digits,
+/// `_`, and `-`, and must not start with `-` (so it is never mistaken for a flag). This keeps names
+/// safe to type on the command line and to use as shell aliases. Names can also be grouped into
+/// namespaces with `/` (e.g., `work/frontend`), as long as no part between the slashes is empty.
 ///
 /// # Returns
-/// * `Ok(())` if the list of shortcuts is successfully retrieved and printed.
-/// * `Err(io::Error)` if an error occurs while loading the shortcuts.
-fn list_shortcuts() -> io::Result<()> {
-    let shortcuts = load_shortcuts()?;
+/// * `Ok(())` if the name is valid.
+/// * `Err(String)` describing the first rule the name breaks.
+fn validate_shortcut_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Shortcut name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "Shortcut name '{}' is longer than {} characters",
+            name, MAX_NAME_LENGTH
+        ));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/')))
+    {
+        return Err(format!(
+            "Shortcut name '{}' contains {:?}; only letters, digits, '_', '-' and '/' are allowed",
+            name, c
+        ));
+    }
+    if name.starts_with('-') {
+        return Err(format!("Shortcut name '{}' cannot start with '-'", name));
+    }
+    if name.split('/').any(str::is_empty) {
+        return Err(format!(
+            "Shortcut name '{}' cannot start or end with '/' or contain '//'",
+            name
+        ));
+    }
+    Ok(())
+}
 
-    if shortcuts.is_empty() {
-        println!("No shortcuts found.");
-    } else {
-        for shortcut in shortcuts {
-            println!("{}: {:?}", shortcut.project_name, shortcut.run_command);
+/// The configuration file path given with the global `--config` flag, if any.
+static CONFIG_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Sets (or clears, with `None`) the configuration file path used instead of the default location.
+fn set_config_override(path: Option<PathBuf>) {
+    *CONFIG_OVERRIDE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = path;
+}
+
+/// The format the configuration file is stored in.
+#[derive(ValueEnum, Default, Debug, Clone, Copy, PartialEq)]
+enum ConfigFormat {
+    /// JSON, the default
+    #[default]
+    Json,
+    /// TOML, which is easier to edit by hand
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Picks the format from a file's extension: TOML for `.toml` files, JSON otherwise.
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
         }
     }
-    Ok(())
 }
 
-/// Opens the project folder associated with the given shortcut name.
-///
-/// This function searches for a shortcut with the specified name, retrieves the first command
-/// from the shortcut's `run_command` (assumed to be the project folder path), and opens that folder
-/// using the appropriate system file manager. If no valid shortcut is found or if there is an issue
-/// with the folder path, an error message is printed.
-///
-/// # Arguments
-/// * `name` - The name of the project whose folder is to be opened.
-///
-/// # Returns
-/// * `Ok(())` if the folder is successfully opened.
-/// * `Err(io::Error)` if an error occurs while retrieving the shortcut or opening the folder.
-///
-/// # Errors
-/// The function will return an error if:
-/// - No shortcut with the given name is found.
-/// - The `run_command` for the shortcut is empty.
-/// - The folder path is invalid or cannot be determined from the run command.
-/// - The operating system is unsupported (other than Windows, macOS, or Linux).
-fn open_project_folder(name: &str) -> io::Result<()> {
-    let shortcuts = load_shortcuts()?;
-    if let Some(shortcut) = shortcuts.iter().find(|s| s.project_name == name) {
-        println!("Opening project folder for: {:?}", shortcut.project_name);
+/// The configuration file format given with the global `--config-format` flag, if any.
+static CONFIG_FORMAT_OVERRIDE: RwLock<Option<ConfigFormat>> = RwLock::new(None);
 
-        if let Some(first_command) = shortcut.run_command.first() {
-            let path = std::path::Path::new(first_command);
+/// Sets (or clears, with `None`) the configuration file format used instead of the one picked from
+/// the file's extension.
+fn set_config_format_override(format: Option<ConfigFormat>) {
+    *CONFIG_FORMAT_OVERRIDE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = format;
+}
 
-            // Get the directory of the path
-            let dir = if path.is_dir() {
-                path
-            } else if let Some(parent) = path.parent() {
-                parent
-            } else {
-                return Err(io::Error::new(
-                    io::ErrorKind::NotFound,
-                    "Unable to determine directory from run command",
-                ));
-            };
+/// Returns the format of the configuration file: the one given with `--config-format`, or else the
+/// one matching the extension of `config_file_path()` (see `ConfigFormat::from_path`).
+fn config_format() -> ConfigFormat {
+    let format = *CONFIG_FORMAT_OVERRIDE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    format.unwrap_or_else(|| ConfigFormat::from_path(&config_file_path()))
+}
 
-            // Open the directory using system file manager
-            let open_command = if cfg!(target_os = "windows") {
-                "explorer"
-            } else if cfg!(target_os = "macos") {
-                "open"
-            } else if cfg!(target_os = "linux") {
-                "xdg-open"
-            } else {
-                return Err(io::Error::new(
-                    io::ErrorKind::Unsupported,
-                    "Unsupported operating system",
-                ));
-            };
+/// Returns the location where older versions of projexts stored the configuration file.
+///
+/// # Panics
+/// This function will panic if the home directory cannot be determined.
+fn legacy_config_file_path() -> PathBuf {
+    dirs::home_dir().unwrap().join(".projexts_config.json")
+}
 
-            Command::new(open_command).arg(dir).spawn()?.wait()?; // Wait for the command to complete
-        } else {
-            eprintln!("Error: Run command is empty for project '{}'", name);
-        }
+/// Returns the platform's default location for the configuration file.
+///
+/// - On macOS this is `~/.projexts_config.json`, as it has always been.
+/// - On Windows this is `%APPDATA%\projexts\config.json`.
+/// - Elsewhere this follows the XDG Base Directory Specification: `$XDG_CONFIG_HOME/projexts/config.json`,
+///   falling back to `~/.config/projexts/config.json` when `XDG_CONFIG_HOME` is unset.
+///
+/// # Panics
+/// This function will panic if the home or configuration directory cannot be determined (e.g., in
+/// environments without a user home directory, such as some containerized or restricted systems).
+fn default_config_file_path() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        legacy_config_file_path()
     } else {
-        eprintln!("Error: No shortcut found with name '{}'", name);
+        // `dirs::config_dir` resolves `$XDG_CONFIG_HOME` (or `~/.config`) on Linux and `%APPDATA%` on Windows
+        dirs::config_dir()
+            .unwrap()
+            .join("projexts")
+            .join("config.json")
     }
-    Ok(())
 }
 
-/// Runs the command associated with a given shortcut, with optional additional arguments.
+/// Returns the path to the configuration file for storing shortcuts.
 ///
-/// This function searches for a shortcut by name, retrieves the associated command, and runs it with
-/// the stored arguments combined with any additional arguments provided by the user. The command is
-/// executed and the function waits for it to complete before returning.
+/// The path is resolved in order of precedence:
+/// 1. The path given with the global `--config` flag, or the file of the profile given with the
+///    global `--profile` flag.
+/// 2. The `PROJEXTS_CONFIG` environment variable, if set and not empty.
+/// 3. The file of the active profile chosen with `profile-switch`, if any.
+/// 4. The platform default from `default_config_file_path()`.
 ///
-/// # Arguments
-/// * `name` - The name of the project whose associated command is to be run.
-/// * `extra_args` - A vector of extra arguments to append to the command’s stored arguments.
+/// # Panics
+/// This function will panic if no override is given and the default location cannot be determined.
+fn config_file_path() -> PathBuf {
+    let path = CONFIG_OVERRIDE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    if let Some(path) = path {
+        return path;
+    }
+    match std::env::var_os("PROJEXTS_CONFIG") {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => {
+            let dir = profiles_root();
+            match active_profile(&dir) {
+                Some(profile) => profile_path(&dir, &profile),
+                None => default_config_file_path(),
+            }
+        }
+    }
+}
+
+/// The name of the profile that stands for the default configuration file.
+const DEFAULT_PROFILE: &str = "default";
+
+/// Returns the directory holding the `profiles` directory and the `active_profile` file, e.g.
+/// `~/.config/projexts` on Linux.
 ///
-/// # Returns
-/// * `Ok(())` if the command is executed successfully.
-/// * `Err(io::Error)` if an error occurs while retrieving the shortcut or running the command.
+/// # Panics
+/// This function will panic if the configuration directory cannot be determined.
+fn profiles_root() -> PathBuf {
+    dirs::config_dir().unwrap().join("projexts")
+}
+
+/// Returns the configuration file of the profile `name` below `root` (see `profiles_root`).
+fn profile_path(root: &Path, name: &str) -> PathBuf {
+    root.join("profiles").join(format!("{}.json", name))
+}
+
+/// Returns the profile chosen with `profile-switch`, or `None` if the default one is active.
+fn active_profile(root: &Path) -> Option<String> {
+    let name = fs::read_to_string(root.join("active_profile")).ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty() && name != DEFAULT_PROFILE)
+}
+
+/// Checks that `name` can be used as a profile name: a valid shortcut name without `/`.
 ///
 /// # Errors
-/// The function will return an error if:
-/// - No shortcut with the given name is found.
-/// - The `run_command` for the shortcut is empty.
-/// - An error occurs when trying to spawn or wait for the command to finish.
-fn run_shortcut(name: &str, extra_args: Vec<String>) -> io::Result<()> {
-    let shortcuts = load_shortcuts()?;
-    if let Some(shortcut) = shortcuts.iter().find(|s| s.project_name == name) {
-        println!("Running command: {:?}", shortcut.run_command);
-
-        if let Some((command, args)) = shortcut.run_command.split_first() {
-            // Combine stored args with extra args
-            let combined_args: Vec<String> = args.iter().cloned().chain(extra_args).collect();
+/// Returns `io::ErrorKind::InvalidInput` if the name is not valid.
+fn validate_profile_name(name: &str) -> io::Result<()> {
+    let result = if name.contains('/') {
+        Err(format!("Profile name '{}' cannot contain '/'", name))
+    } else {
+        validate_shortcut_name(name).map_err(|e| e.replace("Shortcut name", "Profile name"))
+    };
+    result.map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
 
-            Command::new(command).args(&combined_args).spawn()?.wait()?; // Wait for the command to complete
-        } else {
-            eprintln!("Error: Command for '{}' is empty.", name);
-        }
+/// Checks that the profile `name` exists below `root`. The default profile always exists.
+///
+/// # Errors
+/// Returns `io::ErrorKind::NotFound` if there is no such profile.
+fn check_profile_exists(root: &Path, name: &str) -> io::Result<()> {
+    if name == DEFAULT_PROFILE || profile_path(root, name).exists() {
+        Ok(())
     } else {
-        eprintln!("Error: No shortcut found with name '{}'", name);
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "No profile named '{}'. Create it with 'projexts profile-create {}'.",
+                name, name
+            ),
+        ))
     }
-    Ok(())
 }
 
-/// Updates the command of an existing shortcut.
+/// Returns the names of all profiles below `root`: the default profile first, then the others
+/// in alphabetical order.
 ///
-/// This function searches for a shortcut by its name and updates its associated command if found.
-/// If a new command is provided, it replaces the existing command for that shortcut. If the shortcut
-/// is found and updated successfully, the changes are saved to storage.
+/// # Errors
+/// Returns an error if the `profiles` directory exists but cannot be read.
+fn list_profiles(root: &Path) -> io::Result<Vec<String>> {
+    let mut names = Vec::new();
+    match fs::read_dir(root.join("profiles")) {
+        Ok(entries) => {
+            for entry in entries {
+                let path = entry?.path();
+                if path
+                    .extension()
+                    .is_some_and(|extension| extension == "json")
+                {
+                    if let Some(stem) = path.file_stem() {
+                        names.push(stem.to_string_lossy().to_string());
+                    }
+                }
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(names)
+}
+
+/// Creates the profile `name` below `root` with no shortcuts.
 ///
-/// # Arguments
-/// * `name` - The name of the shortcut to update.
-/// * `new_command` - An optional vector of new command arguments. If `Some(command)` is provided,
-///   the command associated with the shortcut will be replaced with this new command. If `None` is
-///   provided, the command will not be changed.
+/// # Errors
+/// Returns `io::ErrorKind::InvalidInput` if the name is not valid, `io::ErrorKind::AlreadyExists`
+/// if the profile exists, and an error if its file cannot be written.
+fn create_profile(root: &Path, name: &str) -> io::Result<()> {
+    validate_profile_name(name)?;
+    let path = profile_path(root, name);
+    if name == DEFAULT_PROFILE || path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("A profile named '{}' already exists", name),
+        ));
+    }
+    fs::create_dir_all(path.parent().unwrap_or(root))?;
+    fs::write(path, serialize_config(&[])?)
+}
+
+/// Deletes the profile `name` below `root` with all of its shortcuts. If it was the active
+/// profile, the default profile becomes active.
 ///
-/// # Returns
-/// * `Ok(())` if the shortcut is found and updated successfully, and the changes are saved.
-/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts, or if the shortcut
-///   with the given name is not found.
+/// # Errors
+/// Returns `io::ErrorKind::InvalidInput` for the default profile, which cannot be deleted,
+/// `io::ErrorKind::NotFound` if there is no such profile, and an error if a file cannot be removed.
+fn delete_profile(root: &Path, name: &str) -> io::Result<()> {
+    if name == DEFAULT_PROFILE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The default profile cannot be deleted",
+        ));
+    }
+    check_profile_exists(root, name)?;
+    fs::remove_file(profile_path(root, name))?;
+    if active_profile(root).as_deref() == Some(name) {
+        switch_profile(root, DEFAULT_PROFILE)?;
+    }
+    Ok(())
+}
+
+/// Makes `name` the active profile below `root`, used whenever no `--profile`, `--config`, or
+/// `PROJEXTS_CONFIG` is given.
 ///
 /// # Errors
-/// The function will return an error if:
-/// - No shortcut with the given name is found.
-/// - An error occurs while saving the updated list of shortcuts to storage.
-fn update_shortcut(name: &str, new_command: Option<Vec<String>>) -> io::Result<()> {
-    let mut shortcuts = load_shortcuts()?;
-    if let Some(shortcut) = shortcuts.iter_mut().find(|s| s.project_name == name) {
-        if let Some(new_command) = new_command {
-            shortcut.run_command = new_command;
+/// Returns `io::ErrorKind::NotFound` if there is no such profile, and an error if the choice cannot
+/// be saved.
+fn switch_profile(root: &Path, name: &str) -> io::Result<()> {
+    check_profile_exists(root, name)?;
+    let marker = root.join("active_profile");
+    if name == DEFAULT_PROFILE {
+        match fs::remove_file(marker) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
         }
-        save_shortcuts(&shortcuts)?;
-        println!("Shortcut '{}' updated successfully.", name);
     } else {
-        eprintln!("Error: No shortcut found with name '{}'", name);
+        fs::create_dir_all(root)?;
+        fs::write(marker, format!("{}\n", name))
     }
-    Ok(())
 }
 
-/// Opens a file from a shortcut's command list.
+/// Moves a configuration file from an old location to a new one.
 ///
-/// This function searches for a shortcut by its name and attempts to open each file path in the shortcut's
-/// command list. The file paths are opened using the system's default file manager. The function will open
-/// each file path as long as the path exists and is a valid file.
-///
-/// # Arguments
-/// * `name` - The name of the shortcut whose command list will be used to find and open the file paths.
+/// Nothing happens if there is no file at `old` or if a file already exists at `new`, so an existing
+/// configuration is never overwritten. The parent directory of `new` is created if needed.
 ///
 /// # Returns
-/// * `Ok(())` if the file(s) were opened successfully.
-/// * `Err(io::Error)` if an error occurs while loading the shortcuts, or if the shortcut with the given
-///   name is not found, or if any file in the shortcut's command list cannot be opened.
-///
+/// * `Ok(true)` if the file was moved.
+/// * `Ok(false)` if there was nothing to migrate.
+/// * `Err(io::Error)` if the directory cannot be created or the file cannot be moved.
+fn migrate_config_file(old: &Path, new: &Path) -> io::Result<bool> {
+    if old == new || !old.exists() || new.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = new.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // A rename fails across filesystems, so fall back to copying the file over
+    if fs::rename(old, new).is_err() {
+        fs::copy(old, new)?;
+        fs::remove_file(old)?;
+    }
+    Ok(true)
+}
+
+/// Returns the configuration file path with `suffix` appended, e.g. `config.json.bak`.
+fn config_sibling_path(suffix: &str) -> PathBuf {
+    let mut path = config_file_path().into_os_string();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Resets the shortcuts by removing the configuration file.
+///
+/// This function deletes the configuration file associated with the shortcuts,
+/// effectively resetting all the stored shortcuts. It does so by calling the
+/// `config_file_path()` function to get the path of the configuration file and
+/// then removing that file from the filesystem. The file is first copied to a `.bak` file next to
+/// it, which `restore_shortcuts` puts back.
+///
+/// # Returns
+/// - `Ok(())` if the file is successfully removed.
+/// - `Err(io::Error)` if there is an error removing the file (e.g., file not found or permission issues).
+///
 /// # Errors
-/// The function will return an error if:
-/// - No shortcut with the given name is found.
-/// - Any of the paths in the shortcut are invalid, do not exist, or are not files.
-/// - The operating system is unsupported for file opening commands.
-fn open_file_from_shortcut(name: &str) -> io::Result<()> {
-    let shortcuts = load_shortcuts()?;
-    if let Some(shortcut) = shortcuts.iter().find(|s| s.project_name == name) {
-        let open_command = if cfg!(target_os = "windows") {
-            "explorer"
-        } else if cfg!(target_os = "macos") {
-            "open"
-        } else if cfg!(target_os = "linux") {
-            "xdg-open"
-        } else {
+/// This function will return an `io::Result` which may contain an error if there are issues
+/// with file removal, such as the file not existing or lacking the necessary permissions.
+fn reset_shortcuts() -> io::Result<()> {
+    let _lock = lock_config()?;
+    let path = config_file_path();
+    fs::copy(&path, config_sibling_path(".bak"))?;
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+/// Puts back the configuration file saved by the last `reset`.
+///
+/// If a configuration was created since then, it is kept as a timestamped backup (see
+/// `backup_config`) before the restored file replaces it.
+///
+/// # Returns
+/// * `Ok(())` if the backup was moved back into place, replacing the current configuration.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if there is no backup to restore.
+/// * `Err(io::Error)` if the backup cannot be moved.
+fn restore_shortcuts() -> io::Result<()> {
+    let _lock = lock_config()?;
+    let backup = config_sibling_path(".bak");
+    if !backup.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No backup found at {}", backup.display()),
+        ));
+    }
+    backup_config()?;
+    fs::rename(backup, config_file_path())
+}
+
+/// The number of timestamped backups kept when `PROJEXTS_MAX_BACKUPS` is not set.
+const DEFAULT_MAX_BACKUPS: usize = 5;
+
+/// Returns the directory where `save_shortcuts` keeps timestamped backups.
+///
+/// This is a `backups` directory next to the configuration file when that file lives in its own
+/// `projexts` directory (e.g., `~/.config/projexts/backups`), and a hidden `.projexts-backups`
+/// directory next to it otherwise, so backups never clutter a shared directory such as `~`.
+/// Profiles (in `projexts/profiles`) keep theirs in `backups/profiles`, so a profile named `config`
+/// cannot evict the backups of the default configuration.
+fn backup_dir() -> PathBuf {
+    let path = config_file_path();
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let is_projexts_dir = |dir: &Path| dir.file_name().is_some_and(|name| name == "projexts");
+    if is_projexts_dir(parent) {
+        parent.join("backups")
+    } else if parent.file_name().is_some_and(|name| name == "profiles")
+        && parent.parent().is_some_and(is_projexts_dir)
+    {
+        parent.with_file_name("backups").join("profiles")
+    } else {
+        parent.join(".projexts-backups")
+    }
+}
+
+/// Copies the current configuration file into `backup_dir` under a timestamped name starting with
+/// the file's own name (e.g., `config-<time>.json` or `work-<time>.json` for a profile), then
+/// deletes the oldest backups of that file so that at most `PROJEXTS_MAX_BACKUPS` (default 5)
+/// remain. Setting it to 0 turns backups off.
+///
+/// # Errors
+/// Returns an error if the backup cannot be written or old backups cannot be removed.
+fn backup_config() -> io::Result<()> {
+    let max_backups = std::env::var("PROJEXTS_MAX_BACKUPS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_BACKUPS);
+    let path = config_file_path();
+    // Project-local files are usually kept in version control, which already keeps their history
+    let is_local = path.file_name() == Some(std::ffi::OsStr::new(LOCAL_CONFIG_FILE_NAME));
+    if max_backups == 0 || is_local || !path.exists() {
+        return Ok(());
+    }
+
+    let dir = backup_dir();
+    fs::create_dir_all(&dir)?;
+    // The timestamp is zero-padded, so sorting the names sorts the backups by age
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.6fZ");
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path
+        .extension()
+        .map_or("json".into(), |ext| ext.to_string_lossy());
+    fs::copy(&path, dir.join(format!("{}-{}.{}", stem, stamp, ext)))?;
+
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| is_backup_of(name, &stem, &ext))
+        })
+        .collect();
+    backups.sort();
+    let excess = backups.len().saturating_sub(max_backups);
+    for old in &backups[..excess] {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+/// Returns whether `name` is a backup file written by `backup_config` for a configuration file
+/// named `<stem>.<ext>`, i.e. `<stem>-<timestamp>Z.<ext>`.
+///
+/// The timestamp must parse, which keeps the backups of `work.json` apart from those of a
+/// `work-2.json`.
+fn is_backup_of(name: &str, stem: &str, ext: &str) -> bool {
+    name.strip_prefix(stem)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .and_then(|rest| rest.strip_suffix(ext))
+        .and_then(|rest| rest.strip_suffix("Z."))
+        .is_some_and(|stamp| {
+            chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%S%.f").is_ok()
+        })
+}
+
+thread_local! {
+    /// How many `ConfigLock`s the current thread holds, so nested calls (e.g. `save_shortcuts`
+    /// from within `add_shortcut`) don't wait for a lock their own thread already has.
+    static CONFIG_LOCK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// An exclusive lock on the configuration file, released when dropped.
+///
+/// Only the outermost lock of a thread holds the lock file; nested locks just count the depth.
+struct ConfigLock {
+    file: Option<fs::File>,
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        CONFIG_LOCK_DEPTH.with(|depth| depth.set(depth.get() - 1));
+        if let Some(file) = &self.file {
+            let _ = fs2::FileExt::unlock(file);
+        }
+    }
+}
+
+/// Takes an exclusive advisory lock on the configuration file.
+///
+/// Commands that change shortcuts hold this lock from loading the shortcuts until the changes are
+/// saved, so two projexts processes running at the same time cannot overwrite each other's changes.
+/// The lock is taken on a separate `.lock` file next to the configuration, because saving replaces
+/// the configuration file itself. If another process holds the lock, this waits for up to ten
+/// seconds before giving up.
+///
+/// # Errors
+/// Returns an error if the lock file cannot be created, or with `io::ErrorKind::WouldBlock` if
+/// another process still holds the lock after waiting.
+fn lock_config() -> io::Result<ConfigLock> {
+    if CONFIG_LOCK_DEPTH.with(|depth| depth.get()) > 0 {
+        CONFIG_LOCK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        return Ok(ConfigLock { file: None });
+    }
+
+    let path = config_sibling_path(".lock");
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)?;
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    while let Err(e) = fs2::FileExt::try_lock_exclusive(&file) {
+        if e.raw_os_error() != fs2::lock_contended_error().raw_os_error() {
+            return Err(e);
+        }
+        if std::time::Instant::now() >= deadline {
             return Err(io::Error::new(
-                io::ErrorKind::Unsupported,
-                "Unsupported operating system",
+                io::ErrorKind::WouldBlock,
+                "Another projexts process is changing the configuration. Try again later.",
             ));
-        };
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    CONFIG_LOCK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    Ok(ConfigLock { file: Some(file) })
+}
 
-        for file_path in &shortcut.run_command {
-            let path = Path::new(file_path);
+/// The version of the configuration file format written by this build of projexts.
+///
+/// Version 0 was a bare JSON array of shortcuts; version 1 wraps it in a `ConfigFile` object.
+const CONFIG_VERSION: u32 = 1;
 
-            if path.exists() && path.is_file() {
-                Command::new(open_command).arg(path).spawn()?.wait()?; // Wait for the command to complete
-                println!("Opening file: {:?}", file_path);
-            } else {
-                eprintln!("Error: '{}' does not exist or is not a file.", file_path);
-            }
+/// The contents of the configuration file: the format version and the stored shortcuts.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct ConfigFile {
+    /// The format version the file was written with (see `CONFIG_VERSION`). A file without one is
+    /// read as version 0.
+    #[serde(default)]
+    version: u32,
+    /// The stored shortcuts, in the order they were added.
+    shortcuts: Vec<Shortcut>,
+}
+
+/// Parses the contents of a configuration file.
+///
+/// Files in the current format are objects with a `version` and a `shortcuts` array. Files written
+/// before the format was versioned are a bare array of shortcuts and are read as version 0, as are
+/// objects without a `version`.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::InvalidData` if `data` is not valid JSON in either format,
+/// if it is an object without a `shortcuts` array, or if it was written by a newer version of
+/// projexts that uses a format this build does not know.
+fn parse_config(data: &str) -> io::Result<Vec<Shortcut>> {
+    let value: serde_json::Value = serde_json::from_str(data)?;
+    if value.is_array() {
+        return Ok(serde_json::from_value(value)?);
+    }
+    // Check the version before the rest, since a newer format may look entirely different
+    check_config_version(value.get("version").and_then(|v| v.as_u64()).unwrap_or(0))?;
+    if value.get("shortcuts").is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "The configuration file is not a projexts configuration: it has no `shortcuts` list",
+        ));
+    }
+    let config: ConfigFile = serde_json::from_value(value)?;
+    Ok(config.shortcuts)
+}
+
+/// Checks that a configuration file's format version is one this build can read.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::InvalidData` if the file was written by a newer version of
+/// projexts.
+fn check_config_version(version: u64) -> io::Result<()> {
+    if version > u64::from(CONFIG_VERSION) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "The configuration file uses format version {}, but this version of projexts only supports up to version {}. Please upgrade projexts.",
+                version, CONFIG_VERSION
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Parses the contents of a TOML configuration file: a `version` key followed by a
+/// `[[shortcuts]]` table for each shortcut, with the same fields as in JSON.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::InvalidData` if `data` is not valid TOML in this format,
+/// or if it was written by a newer version of projexts.
+fn parse_toml_config(data: &str) -> io::Result<Vec<Shortcut>> {
+    let invalid = |e: toml::de::Error| io::Error::new(io::ErrorKind::InvalidData, e);
+    let value: toml::Table = toml::from_str(data).map_err(invalid)?;
+    check_config_version(
+        value
+            .get("version")
+            .and_then(|v| v.as_integer())
+            .map_or(0, |v| v.max(0) as u64),
+    )?;
+    // An empty file has no `shortcuts` array at all
+    if !value.contains_key("shortcuts") {
+        return Ok(Vec::new());
+    }
+    let config: ConfigFile = value.try_into().map_err(invalid)?;
+    Ok(config.shortcuts)
+}
+
+/// Serializes shortcuts as a TOML configuration file (see `parse_toml_config`).
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::InvalidData` if a shortcut cannot be represented in TOML.
+fn serialize_toml_config(shortcuts: &[Shortcut]) -> io::Result<String> {
+    let config = ConfigFile {
+        version: CONFIG_VERSION,
+        shortcuts: shortcuts.to_vec(),
+    };
+    toml::to_string_pretty(&config).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Loads shortcuts from a TOML file.
+///
+/// # Arguments
+/// * `path` - The TOML file to read.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or is not a valid TOML configuration.
+fn load_shortcuts_from_toml(path: &Path) -> io::Result<Vec<Shortcut>> {
+    parse_toml_config(&fs::read_to_string(path)?)
+}
+
+/// Saves shortcuts to a TOML file, replacing it atomically (see `write_config_file`).
+///
+/// # Arguments
+/// * `shortcuts` - The shortcuts to save.
+/// * `path` - The file to write.
+///
+/// # Errors
+/// Returns an error if the shortcuts cannot be serialized or the file cannot be written.
+fn save_shortcuts_as_toml(shortcuts: &[Shortcut], path: &Path) -> io::Result<()> {
+    write_config_file(path, &serialize_toml_config(shortcuts)?)
+}
+
+/// Serializes shortcuts in the current configuration file format.
+fn serialize_config(shortcuts: &[Shortcut]) -> io::Result<String> {
+    let config = ConfigFile {
+        version: CONFIG_VERSION,
+        shortcuts: shortcuts.to_vec(),
+    };
+    Ok(serde_json::to_string_pretty(&config)?)
+}
+
+/// Loads the list of shortcuts from the persistent storage file.
+///
+/// This function checks if the configuration file exists at the specified path. If the file does not
+/// exist, it creates a new, empty configuration file. After ensuring the file exists, it reads the
+/// data from the file, parses it with `parse_config` (or `load_shortcuts_from_toml` for a TOML
+/// file, see `config_format`), and returns the list of shortcuts.
+///
+/// # Errors
+/// This function may return an error if:
+/// - The configuration file cannot be read (e.g., due to I/O errors).
+/// - The file content cannot be parsed, or was written by a newer version of projexts.
+/// - There is an error while creating the file if it doesn't exist.
+fn load_shortcuts() -> io::Result<Vec<Shortcut>> {
+    let path = config_file_path();
+    debug_log!(
+        "load_shortcuts: reading {:?} as {:?}",
+        path,
+        config_format()
+    );
+    if !path.exists() {
+        log!("Creating storage for shortcuts...");
+        // Create an empty file if it doesn't exist
+        save_shortcuts(&[])?;
+    }
+    match config_format() {
+        ConfigFormat::Json => parse_config(&fs::read_to_string(path)?),
+        ConfigFormat::Toml => load_shortcuts_from_toml(&path),
+    }
+}
+
+/// The name of a project-local configuration file, looked up from the current directory upwards.
+const LOCAL_CONFIG_FILE_NAME: &str = ".projexts.json";
+
+/// Finds the nearest project-local `.projexts.json`, starting at `start` and walking up the parent
+/// directories.
+///
+/// The search stops after checking `home`. If `start` is not inside `home`, every ancestor up to
+/// the root is checked.
+///
+/// # Arguments
+/// * `start` - The directory to start searching from.
+/// * `home` - The last directory to check, usually the user's home directory.
+///
+/// # Returns
+/// The path of the nearest `.projexts.json`, or `None` if there is none.
+fn find_local_config_from(start: &Path, home: Option<&Path>) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        let candidate = dir.join(LOCAL_CONFIG_FILE_NAME);
+        if candidate.is_file() {
+            return Some(candidate);
         }
-    } else {
-        eprintln!("Error: No shortcut found with name '{}'", name);
+        if Some(dir) == home {
+            break;
+        }
+    }
+    None
+}
+
+/// Finds the nearest project-local `.projexts.json` in the current directory or its parents, up
+/// to the home directory (see `find_local_config_from`).
+///
+/// # Returns
+/// The path of the nearest `.projexts.json`, or `None` if there is none or the current directory
+/// cannot be determined.
+fn find_local_config() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    find_local_config_from(&cwd, dirs::home_dir().as_deref())
+}
+
+/// Merges the global shortcuts with the shortcuts of a project-local configuration.
+///
+/// A local shortcut shadows a global one with the same name. The remaining global shortcuts keep
+/// their order and the local shortcuts are listed after them.
+///
+/// # Arguments
+/// * `global` - The shortcuts from the global configuration file.
+/// * `local` - The shortcuts from the nearest `.projexts.json`.
+///
+/// # Returns
+/// The merged list of shortcuts.
+fn merge_shortcut_lists(global: Vec<Shortcut>, local: Vec<Shortcut>) -> Vec<Shortcut> {
+    let mut merged: Vec<Shortcut> = global
+        .into_iter()
+        .filter(|shortcut| {
+            !local
+                .iter()
+                .any(|l| names_match(&l.project_name, &shortcut.project_name))
+        })
+        .collect();
+    merged.extend(local);
+    merged
+}
+
+/// Loads the shortcuts of the nearest project-local `.projexts.json`, if any.
+///
+/// Nothing is loaded when the local file is the configuration file itself (e.g., chosen with
+/// `--config`), so its shortcuts are not listed twice.
+///
+/// # Errors
+/// Returns an error if the local file cannot be read or parsed.
+fn load_local_shortcuts() -> io::Result<Vec<Shortcut>> {
+    match find_local_config() {
+        Some(path) if !same_file(&path, &config_file_path()) => {
+            debug_log!("load_local_shortcuts: reading {:?}", path);
+            parse_config(&fs::read_to_string(path)?)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Returns whether two paths point to the same file, comparing canonical paths when possible.
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Loads every shortcut visible from the current directory: the global shortcuts merged with the
+/// ones from the nearest `.projexts.json` (see `merge_shortcut_lists`).
+///
+/// Commands that only look shortcuts up use this; commands that change shortcuts use
+/// `load_shortcuts`, which only reads the configuration file that is written back.
+///
+/// # Errors
+/// Returns an error if either configuration cannot be loaded.
+fn load_visible_shortcuts() -> io::Result<Vec<Shortcut>> {
+    Ok(merge_shortcut_lists(
+        load_shortcuts()?,
+        load_local_shortcuts()?,
+    ))
+}
+
+/// Saves the given list of shortcuts to persistent storage.
+///
+/// This function serializes the provided vector of `Shortcut` objects into the JSON
+/// configuration format (see `ConfigFile`), or TOML for a TOML configuration (see `config_format`),
+/// and writes it to the configuration file with `write_config_file`. If the operation is successful,
+/// the updated list of shortcuts will be stored and available for future access.
+///
+/// Before that, the previous version of the file is kept as a timestamped backup (see
+/// `backup_config`). Bookkeeping that is not a change made by the user, such as recording a run,
+/// uses `write_shortcuts` instead, so it does not rotate the user's backups out.
+///
+/// # Parameters
+/// - `shortcuts`: A slice of `Shortcut` objects that represents the list of shortcuts
+///   to be saved. Each `Shortcut` contains a project name and the associated run command.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The `shortcuts` cannot be serialized.
+/// - The temporary file cannot be created or written, or cannot be renamed to the storage file.
+fn save_shortcuts(shortcuts: &[Shortcut]) -> io::Result<()> {
+    let _lock = lock_config()?;
+    backup_config()?;
+    write_shortcuts(shortcuts)
+}
+
+/// Writes the given list of shortcuts to the configuration file like `save_shortcuts`, but without
+/// taking a backup first.
+///
+/// # Errors
+/// Returns an error if the shortcuts cannot be serialized or the configuration file cannot be
+/// written.
+fn write_shortcuts(shortcuts: &[Shortcut]) -> io::Result<()> {
+    let _lock = lock_config()?;
+    let path = config_file_path();
+    debug_log!(
+        "save_shortcuts: writing {} shortcut(s) to {:?}",
+        shortcuts.len(),
+        path
+    );
+    match config_format() {
+        ConfigFormat::Json => write_config_file(&path, &serialize_config(shortcuts)?),
+        ConfigFormat::Toml => save_shortcuts_as_toml(shortcuts, &path),
     }
+}
+
+/// Writes `data` to the configuration file at `path`, creating its directory if needed.
+///
+/// The data is first written to a temporary file in the same directory, which is then renamed over
+/// the configuration file. The rename is atomic, so the configuration is never left half-written.
+///
+/// # Errors
+/// Returns an error if the temporary file cannot be created or written, or cannot be renamed to
+/// `path`.
+fn write_config_file(path: &Path, data: &str) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+
+    // Write to a temporary file next to the configuration and rename it into place, so a crash
+    // mid-write leaves either the old or the new file, never a truncated one
+    let mut file = tempfile::NamedTempFile::new_in(parent)?;
+    file.write_all(data.as_bytes())?;
+    file.as_file().sync_all()?;
+    file.persist(path).map_err(|e| e.error)?;
     Ok(())
 }
 
-/// Commits and pushes changes to a Git repository using a shortcut's project directory.
+/// Asks a yes/no question on stdin and returns the answer.
+///
+/// `prompt` is printed followed by `[Y/n]` or `[y/N]` depending on `default`, which is also the
+/// answer used when the user just presses Enter or stdin is closed.
+fn confirm(prompt: &str, default: bool) -> io::Result<bool> {
+    print!("{} {} ", prompt, if default { "[Y/n]:" } else { "[y/N]:" });
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    })
+}
+
+/// Prints `prompt` and reads one line from `input`, without the trailing newline.
+///
+/// # Errors
+/// Returns `io::ErrorKind::UnexpectedEof` if `input` is closed before a line is read.
+fn prompt_line<R: io::BufRead>(input: &mut R, prompt: &str) -> io::Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Input ended before the shortcut was complete",
+        ));
+    }
+    Ok(line.trim().to_string())
+}
+
+/// Reads a command for `add --stdin`, one token per line until the end of the input.
 ///
-/// This function finds the shortcut associated with the given `name`, navigates to the project directory
-/// specified in the shortcut's `run_command`, and performs a `git add`, `git commit`, and `git push` with the
-/// specified commit message.
+/// Each line becomes one argument exactly as written, so tokens may contain spaces or quotes
+/// without any escaping. Line endings are removed and empty lines are skipped.
 ///
 /// # Arguments
-/// * `name` - The name of the shortcut whose associated Git project will be used.
-/// * `commit_message` - The commit message to use for the `git commit` command.
+/// * `input` - Where the command is read from, usually stdin.
+///
+/// # Errors
+/// Returns an error if `input` cannot be read or is not valid UTF-8.
+fn read_command_lines<R: io::BufRead>(input: R) -> io::Result<Vec<String>> {
+    let mut command = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        let token = line.strip_suffix('\r').unwrap_or(&line);
+        if !token.is_empty() {
+            command.push(token.to_string());
+        }
+    }
+    Ok(command)
+}
+
+/// Asks for the fields of a new shortcut one line at a time, for `add-interactive`.
+///
+/// Each answer is checked before moving on to the next question, and the question is repeated
+/// after an explanation when the answer is invalid: the name must be valid (see
+/// `validate_shortcut_name`) and not taken yet; the program must exist; and the working directory must be an
+/// existing directory. An empty working directory means the current one, and empty tags or
+/// description are left out.
+///
+/// # Arguments
+/// * `input` - Where the answers are read from, usually stdin.
 ///
 /// # Returns
-/// * `Ok(())` if the Git operations (add, commit, push) were successful.
-/// * `Err(io::Error)` if any error occurs during the Git operations, loading shortcuts, or if the shortcut
-///   cannot be found.
+/// The name, the command (program followed by its arguments), and the options to pass to
+/// `add_shortcut`.
 ///
 /// # Errors
-/// The function will return an error if:
-/// - No shortcut with the given name is found.
-/// - The directory from the shortcut's `run_command` cannot be determined or is invalid.
-/// - Any of the Git commands (`git add`, `git commit`, `git push`) fail.
-fn git_push(name: &str, commit_message: &str) -> io::Result<()> {
+/// Returns an error if `input` ends early or the shortcuts cannot be loaded.
+fn prompt_new_shortcut<R: io::BufRead>(
+    input: &mut R,
+) -> io::Result<(String, Vec<String>, AddOptions)> {
     let shortcuts = load_shortcuts()?;
-    if let Some(shortcut) = shortcuts.iter().find(|s| s.project_name == name) {
-        if let Some(first_command) = shortcut.run_command.first() {
-            let path = Path::new(first_command);
-
-            let dir = if path.is_dir() {
-                path
-            } else if let Some(parent) = path.parent() {
-                parent
-            } else {
-                return Err(io::Error::new(
-                    io::ErrorKind::NotFound,
-                    "Unable to determine directory from run command",
-                ));
-            };
+    let name = loop {
+        let name = prompt_line(input, "Project name: ")?;
+        if let Err(e) = validate_shortcut_name(&name) {
+            eprintln!("{}", failure(&e));
+        } else if shortcuts
+            .iter()
+            .any(|s| names_match(&s.project_name, &name))
+        {
+            eprintln!(
+                "{}",
+                failure(&format!("A shortcut named '{}' already exists.", name))
+            );
+        } else {
+            break name;
+        }
+    };
+    let mut command = loop {
+        let program = prompt_line(input, "Command (executable): ")?;
+        if program.is_empty() {
+            eprintln!("{}", failure("The command cannot be empty."));
+        } else if !program_exists(&program) {
+            eprintln!("{}", failure(&format!("'{}' was not found.", program)));
+        } else {
+            break vec![program];
+        }
+    };
+    command.extend(split_shell_words(&prompt_line(
+        input,
+        "Arguments (space-separated): ",
+    )?));
+    let working_dir = loop {
+        let dir = prompt_line(input, "Working directory [current]: ")?;
+        let dir = if dir.is_empty() {
+            std::env::current_dir()?
+        } else {
+            PathBuf::from(dir)
+        };
+        match resolve_working_dir(&dir) {
+            Ok(dir) => break dir,
+            Err(e) => eprintln!("{}", failure(&e.to_string())),
+        }
+    };
+    let tags = prompt_line(input, "Tags (comma-separated): ")?
+        .split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    let description = Some(prompt_line(input, "Description: ")?).filter(|d| !d.is_empty());
+
+    let options = AddOptions {
+        tags,
+        description,
+        working_dir: Some(working_dir),
+        ..Default::default()
+    };
+    Ok((name, command, options))
+}
+
+/// Returns the editor command to open files with, split into the program and its arguments.
+///
+/// `editor` is the value of the `EDITOR` environment variable, which may include arguments (e.g.,
+/// `code --wait`). When it is unset or blank, `notepad` is used on Windows and `vi` elsewhere.
+fn editor_command(editor: Option<String>) -> Vec<String> {
+    let words = editor
+        .map(|editor| split_shell_words(&editor))
+        .unwrap_or_default();
+    if !words.is_empty() {
+        words
+    } else if cfg!(target_os = "windows") {
+        vec!["notepad".to_string()]
+    } else {
+        vec!["vi".to_string()]
+    }
+}
+
+/// Opens the configuration file in the user's editor and checks it afterwards.
+///
+/// Once the editor exits, the file is parsed again, as JSON or TOML depending on the configuration
+/// format (see `config_format`). If it is no longer valid, the error is printed and the user is asked whether to
+/// re-open the editor to fix it; declining restores the file to how it was before editing, so the
+/// configuration is never left corrupt.
+///
+/// # Arguments
+/// * `editor` - The editor command and its arguments, usually from `editor_command`.
+///
+/// # Returns
+/// * `Ok(true)` if the edited configuration is valid and was kept.
+/// * `Ok(false)` if the edits were invalid and the previous configuration was restored.
+/// * `Err(io::Error)` if the editor cannot be started or the file cannot be read or written.
+fn edit_config(editor: &[String]) -> io::Result<bool> {
+    // Make sure there is a file to edit
+    load_shortcuts()?;
+    let path = config_file_path();
+    let original = fs::read_to_string(&path)?;
+
+    loop {
+        let status = Command::new(&editor[0])
+            .args(&editor[1..])
+            .arg(&path)
+            .status()?;
+        if !status.success() {
+            eprintln!("{}", warning(&format!("Editor exited with {}", status)));
+        }
+        let edited = fs::read_to_string(&path)?;
+        let parsed = match config_format() {
+            ConfigFormat::Json => parse_config(&edited),
+            ConfigFormat::Toml => parse_toml_config(&edited),
+        };
+        match parsed {
+            Ok(_) => return Ok(true),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    failure(&format!("The configuration is not valid: {}", e))
+                );
+                if !confirm("Re-open the editor to fix it?", true)? {
+                    // Restore the file the same way saving does, so a concurrent change is not
+                    // interleaved and an interrupted restore does not leave a truncated file
+                    let _lock = lock_config()?;
+                    write_config_file(&path, &original)?;
+                    return Ok(false);
+                }
+            }
+        }
+    }
+}
+
+/// Options that control how `add_shortcut` stores a new shortcut.
+#[derive(Default, Debug, Clone)]
+struct AddOptions {
+    /// Overwrite an existing shortcut with the same name instead of returning an error.
+    force: bool,
+    /// Tags used to categorize the shortcut.
+    tags: Vec<String>,
+    /// A short explanation of what the shortcut does.
+    description: Option<String>,
+    /// The directory to run the command from.
+    working_dir: Option<PathBuf>,
+    /// Environment variables to set for the command.
+    env: Vec<(String, String)>,
+    /// Store paths in the command as typed and resolve them when the shortcut runs.
+    lazy_resolve: bool,
+    /// Expand environment variable references in the command now instead of when it runs.
+    expand_env: bool,
+    /// A command to run before the main command.
+    pre_run: Option<Vec<String>>,
+    /// A command to run after the main command.
+    post_run: Option<Vec<String>>,
+    /// The name of a shortcut to copy the fields from. The other options override copied values.
+    copy_from: Option<String>,
+}
+
+/// Options that control which fields `update_shortcut` changes besides the command.
+#[derive(Default, Debug, Clone, PartialEq)]
+struct UpdateOptions {
+    /// Tags to add to the shortcut. Tags it already has are ignored.
+    add_tags: Vec<String>,
+    /// Tags to remove from the shortcut.
+    remove_tags: Vec<String>,
+    /// A new description. An empty string clears the description.
+    description: Option<String>,
+    /// A new directory to run the command from.
+    working_dir: Option<PathBuf>,
+    /// Environment variables to set, replacing any existing value.
+    set_env: Vec<(String, String)>,
+    /// Names of environment variables to stop setting.
+    unset_env: Vec<String>,
+    /// An argument to append to the command.
+    add_arg: Option<String>,
+    /// An argument to remove from the command. Only its first occurrence is removed.
+    remove_arg: Option<String>,
+    /// An argument to insert at the given position among the arguments (0 is the first).
+    insert_arg_at: Option<(usize, String)>,
+    /// A new name for the shortcut.
+    new_name: Option<String>,
+    /// A new command to run before the main command. An empty command removes it.
+    pre_run: Option<Vec<String>>,
+    /// A new command to run after the main command. An empty command removes it.
+    post_run: Option<Vec<String>>,
+    /// Change the shortcut even if it is locked.
+    allow_locked: bool,
+}
+
+/// Parses an `INDEX=ARG` argument into a position and the argument to insert there.
+///
+/// # Errors
+/// Returns an error message if there is no `=` or the index is not a number.
+fn parse_insert_arg(arg: &str) -> Result<(usize, String), String> {
+    match arg.split_once('=') {
+        Some((index, value)) => index
+            .parse()
+            .map(|index| (index, value.to_string()))
+            .map_err(|_| format!("expected INDEX=ARG with a numeric index, got '{}'", arg)),
+        None => Err(format!("expected INDEX=ARG, got '{}'", arg)),
+    }
+}
+
+/// Applies the argument changes in `options` to `command`, in the order remove, insert, append.
+///
+/// The program itself (the first token) is never removed or moved; positions for insertion count
+/// the arguments after it.
+///
+/// # Errors
+/// Returns `io::ErrorKind::InvalidInput` if the argument to remove is not in the command or the
+/// insertion position is past the end of the arguments.
+fn edit_arguments(command: &mut Vec<String>, options: &UpdateOptions) -> io::Result<()> {
+    if let Some(arg) = &options.remove_arg {
+        let index = command
+            .iter()
+            .skip(1)
+            .position(|a| a == arg)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("The command has no argument '{}'", arg),
+                )
+            })?;
+        command.remove(index + 1);
+    }
+    if let Some((index, arg)) = &options.insert_arg_at {
+        let args = command.len().saturating_sub(1);
+        if *index > args {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Cannot insert at position {}; the command has {} argument(s)",
+                    index, args
+                ),
+            ));
+        }
+        command.insert(index + 1, arg.clone());
+    }
+    if let Some(arg) = &options.add_arg {
+        command.push(arg.clone());
+    }
+    Ok(())
+}
+
+/// Parses a `KEY=VALUE` argument into an environment variable name and value.
+///
+/// # Errors
+/// Returns an error message if there is no `=` or the name is empty.
+fn parse_env_var(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{}'", arg)),
+    }
+}
+
+/// A command and its arguments given as a single command-line argument (see `parse_command_line`).
+///
+/// Clap reads a field of type `Option<Vec<String>>` as a flag that takes several values, so flags
+/// taking a whole command are declared with this alias instead.
+type CommandLine = Vec<String>;
+
+/// Splits a command given as a single argument, such as `--pre-run "docker compose up -d"`, into
+/// its words the way a shell would.
+fn parse_command_line(arg: &str) -> Result<CommandLine, String> {
+    Ok(split_shell_words(arg))
+}
+
+/// Returns the environment variables of `shortcut` as `KEY=VALUE` strings, sorted by name.
+fn env_assignments(shortcut: &Shortcut) -> Vec<String> {
+    let mut vars: Vec<String> = shortcut
+        .env
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, shell_quote(value)))
+        .collect();
+    vars.sort();
+    vars
+}
+
+/// Converts `path` to an absolute path and checks that it is a directory.
+///
+/// # Errors
+/// Returns an error if the path does not exist, or `io::ErrorKind::InvalidInput` if it is not a
+/// directory.
+fn resolve_working_dir(path: &Path) -> io::Result<PathBuf> {
+    let absolute = fs::canonicalize(path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "Cannot use '{}' as working directory: {}",
+                path.display(),
+                e
+            ),
+        )
+    })?;
+    if !absolute.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{}' is not a directory", path.display()),
+        ));
+    }
+    Ok(absolute)
+}
+
+/// Returns the current time as an ISO-8601 UTC timestamp with second precision, e.g.
+/// `2024-05-01T10:00:00Z`.
+fn now_timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Adds each tag in `new_tags` to `tags` unless it is already present.
+fn add_tags(tags: &mut Vec<String>, new_tags: &[String]) {
+    for tag in new_tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+}
+
+/// Adds a new shortcut with the given name and command to the storage.
+///
+/// This function adds a new shortcut, consisting of a project name and a command, to the list of stored
+/// shortcuts. It first validates that the command is not empty and then ensures that all paths in the
+/// command are either absolute or can be converted to absolute paths. If any relative paths are provided,
+/// they are converted to absolute paths using `fs::canonicalize()`. If a valid path is not found for any
+/// command component, an error is returned. With `options.lazy_resolve`, the command is stored as
+/// typed instead and its paths are resolved each time it runs. Environment variable references
+/// such as `$HOME` are stored unexpanded and expanded each time the shortcut runs, unless
+/// `options.expand_env` asks for them to be expanded right away. A working directory in `options` is converted to an
+/// absolute path as well, and must exist.
+///
+/// With `options.copy_from`, the new shortcut starts as a copy of that shortcut (without its usage
+/// statistics). A non-empty `command` and the tags, description, working directory, environment
+/// variables and hooks given in `options` then override the copied values.
+///
+/// If a shortcut with the same name already exists, an error is returned so the existing entry is never
+/// shadowed by an unreachable duplicate. With `options.force`, the existing entry is replaced in place
+/// instead, mirroring `update_shortcut`.
+///
+/// # Arguments
+/// * `name` - The name of the project or shortcut.
+/// * `command` - A vector of strings representing the command to run, where each string is a part of the command (e.g., executable name, arguments).
+/// * `options` - Additional options controlling how the shortcut is stored.
+///
+/// # Returns
+/// * `Ok(())` if the shortcut is successfully added to the storage.
+/// * `Err(io::Error)` if the command is empty, or if no valid paths are found in the command.
+/// * `Err(io::Error)` if the working directory does not exist or is not a directory.
+/// * `Err(io::Error)` with `io::ErrorKind::AlreadyExists` if the name is taken and `force` is not set.
+/// * `Err(io::Error)` with `io::ErrorKind::PermissionDenied` if the name belongs to a locked
+///   shortcut, even with `force`.
+/// * `Err(io::Error)` with `io::ErrorKind::InvalidInput` if the name is not a valid shortcut name
+///   (see `validate_shortcut_name`).
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if the shortcut to copy from does not exist.
+fn add_shortcut(name: &str, command: Vec<String>, options: &AddOptions) -> io::Result<()> {
+    debug_log!(
+        "add_shortcut(name: {:?}, command: {:?}, options: {:?})",
+        name,
+        command,
+        options
+    );
+    validate_shortcut_name(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    if command.is_empty() && options.copy_from.is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Command cannot be empty",
+        ));
+    }
+
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    let mut shortcut = match &options.copy_from {
+        Some(source) => {
+            let mut copy = shortcuts
+                .iter()
+                .find(|s| names_match(&s.project_name, source))
+                .cloned()
+                .ok_or_else(|| shortcut_not_found(source))?;
+            copy.locked = false;
+            copy.run_count = 0;
+            copy.last_run_at = None;
+            copy.updated_at = None;
+            copy
+        }
+        None => Shortcut::default(),
+    };
+    shortcut.project_name = name.to_string();
+    shortcut.created_at = Some(now_timestamp());
+
+    if !command.is_empty() {
+        let command: Vec<String> = if options.expand_env {
+            command.iter().map(|token| expand_env_vars(token)).collect()
+        } else {
+            command
+        };
+        (shortcut.run_command, shortcut.resolve) = if options.lazy_resolve {
+            (command, ResolveMode::Lazy)
+        } else {
+            let command = command.iter().map(|token| absolute_token(token)).collect();
+            (command, ResolveMode::Eager)
+        };
+    }
+    if !options.tags.is_empty() {
+        shortcut.tags.clear();
+        add_tags(&mut shortcut.tags, &options.tags);
+    }
+    if let Some(description) = &options.description {
+        shortcut.description = Some(description.clone());
+    }
+    if let Some(dir) = &options.working_dir {
+        shortcut.working_dir = Some(resolve_working_dir(dir)?);
+    }
+    shortcut.env.extend(options.env.iter().cloned());
+    if let Some(hook) = &options.pre_run {
+        shortcut.pre_run = Some(hook.clone()).filter(|hook| !hook.is_empty());
+    }
+    if let Some(hook) = &options.post_run {
+        shortcut.post_run = Some(hook.clone()).filter(|hook| !hook.is_empty());
+    }
+
+    if let Some(existing) = shortcuts
+        .iter_mut()
+        .find(|s| names_match(&s.project_name, name))
+    {
+        if !options.force {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "A shortcut named '{}' already exists. Use --force to overwrite it.",
+                    name
+                ),
+            ));
+        }
+        if existing.locked {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "Shortcut '{}' is locked and cannot be overwritten. Unlock it with 'projexts unlock {}' first.",
+                    name, name
+                ),
+            ));
+        }
+        *existing = shortcut;
+    } else {
+        shortcuts.push(shortcut);
+    }
+    save_shortcuts(&shortcuts)
+}
+
+/// Removes a shortcut with the given name from the storage.
+///
+/// This function searches for a shortcut with the specified `name` and removes it from the list of stored
+/// shortcuts. If no shortcut with the given name is found, an error is returned. If the shortcut is
+/// successfully removed, the list of shortcuts is saved back to storage.
+///
+/// A locked shortcut (see `lock_shortcut`) is only removed with `allow_locked`.
+///
+/// # Arguments
+/// * `name` - The name of the project or shortcut to remove.
+/// * `allow_locked` - Remove the shortcut even if it is locked (`remove --force`).
+///
+/// # Returns
+/// * `Ok(())` if the shortcut is removed successfully.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name`.
+/// * `Err(io::Error)` with `io::ErrorKind::PermissionDenied` if the shortcut is locked and
+///   `allow_locked` is not set.
+/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts.
+fn remove_shortcut(name: &str, allow_locked: bool) -> io::Result<()> {
+    debug_log!(
+        "remove_shortcut(name: {:?}, allow_locked: {})",
+        name,
+        allow_locked
+    );
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    if let Some(shortcut) = shortcuts
+        .iter()
+        .find(|s| s.locked && names_match(&s.project_name, name))
+    {
+        if !allow_locked {
+            return Err(shortcut_locked(&shortcut.project_name));
+        }
+    }
+    let initial_len = shortcuts.len();
+
+    // Retain only shortcuts that do not match the given name
+    shortcuts.retain(|shortcut| !names_match(&shortcut.project_name, name));
+
+    if shortcuts.len() == initial_len {
+        return Err(shortcut_not_found(name));
+    }
+    save_shortcuts(&shortcuts)?;
+    log!(
+        "{}",
+        success(&format!("Shortcut '{}' removed successfully.", name))
+    );
+    Ok(())
+}
+
+/// Shows the shortcut named `name` and asks whether to remove it, for `remove --interactive`.
+///
+/// The question is only asked when stdin is a terminal, so scripts that pipe into projexts are
+/// never blocked by it.
+///
+/// # Returns
+/// * `Ok(true)` if the shortcut should be removed, including when stdin is not a terminal or no
+///   shortcut is named `name` (which `remove_shortcut` reports itself).
+/// * `Ok(false)` if the user declined.
+/// * `Err(io::Error)` if the shortcuts cannot be loaded or the answer cannot be read.
+fn confirm_removal(name: &str) -> io::Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Ok(true);
+    }
+    let shortcuts = load_shortcuts()?;
+    let Some(shortcut) = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+    else {
+        return Ok(true);
+    };
+    print!("{}", ShortcutFormatter::default().verbose(shortcut));
+    confirm(
+        &format!("Remove shortcut '{}'?", shortcut.project_name),
+        false,
+    )
+}
+
+/// Shortens `s` to at most `max` columns, ending it with "..." if anything was cut off.
+///
+/// Columns are counted by display width, like the padding of table columns, so wide characters such
+/// as CJK and most emoji count twice.
+fn truncate(s: &str, max: usize) -> String {
+    if s.width() <= max {
+        return s.to_string();
+    }
+    let budget = max.saturating_sub(3);
+    let mut width = 0;
+    let kept: String = s
+        .chars()
+        .take_while(|c| {
+            width += c.width().unwrap_or(0);
+            width <= budget
+        })
+        .collect();
+    format!("{}...", kept)
+}
+
+/// Formats single shortcuts for the `compact` and `verbose` list views and for `info`.
+#[derive(Debug, Clone)]
+struct ShortcutFormatter {
+    /// The maximum length of a line in the compact format, in columns.
+    compact_width: usize,
+}
+
+impl Default for ShortcutFormatter {
+    fn default() -> Self {
+        ShortcutFormatter { compact_width: 80 }
+    }
+}
+
+impl ShortcutFormatter {
+    /// Formats a shortcut as `<name>: <command>` on one line, cut off at `compact_width`.
+    fn compact(&self, shortcut: &Shortcut) -> String {
+        let line = truncate(
+            &format!(
+                "{}: {}",
+                shortcut.project_name,
+                command_line(&shortcut.run_command)
+            ),
+            self.compact_width,
+        );
+        // Color the name after truncating so the escape codes don't count towards the width
+        match line.strip_prefix(shortcut.project_name.as_str()) {
+            Some(rest) => format!("{}{}", highlight(&shortcut.project_name), rest),
+            None => line,
+        }
+    }
+
+    /// Formats every field of a shortcut as a block of labeled lines, with one command token and
+    /// one environment variable per line. The block ends with a newline.
+    fn verbose(&self, shortcut: &Shortcut) -> String {
+        let mut lines = vec![format!(
+            "Name:        {}",
+            highlight(&shortcut.project_name)
+        )];
+        lines.push("Command:".to_string());
+        for token in &shortcut.run_command {
+            lines.push(format!("  {}", token));
+        }
+        lines.push(format!(
+            "Description: {}",
+            shortcut.description.as_deref().unwrap_or("-")
+        ));
+        if shortcut.tags.is_empty() {
+            lines.push("Tags:        -".to_string());
+        } else {
+            lines.push(format!("Tags:        {}", shortcut.tags.join(", ")));
+        }
+        match &shortcut.working_dir {
+            Some(dir) => lines.push(format!("Working dir: {}", dir.display())),
+            None => lines.push("Working dir: -".to_string()),
+        }
+        let env = env_assignments(shortcut);
+        if env.is_empty() {
+            lines.push("Env:         -".to_string());
+        } else {
+            lines.push("Env:".to_string());
+            for var in env {
+                lines.push(format!("  {}", var));
+            }
+        }
+        for (label, hook) in [
+            ("Pre-run:    ", &shortcut.pre_run),
+            ("Post-run:   ", &shortcut.post_run),
+        ] {
+            match hook {
+                Some(hook) => lines.push(format!("{} {}", label, command_line(hook))),
+                None => lines.push(format!("{} -", label)),
+            }
+        }
+        lines.push(format!("Run count:   {}", shortcut.run_count));
+        lines.push(format!(
+            "Last run:    {}",
+            shortcut.last_run_at.as_deref().unwrap_or("never")
+        ));
+        lines.push(format!(
+            "Created:     {}",
+            shortcut.created_at.as_deref().unwrap_or("-")
+        ));
+        lines.push(format!(
+            "Updated:     {}",
+            shortcut.updated_at.as_deref().unwrap_or("-")
+        ));
+        lines.push(format!(
+            "Locked:      {}",
+            if shortcut.locked { "yes" } else { "no" }
+        ));
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Fills in a template with the fields of a shortcut.
+///
+/// The placeholders `{name}`, `{command}`, `{description}`, `{tags}` (separated by commas), and
+/// `{run_count}` are replaced by the corresponding field; missing fields become empty. Everything
+/// else is copied unchanged, including a `{` that does not start a placeholder.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::InvalidInput` if the template contains a placeholder with
+/// another name, e.g. `{nmae}`.
+fn format_shortcut(shortcut: &Shortcut, template: &str) -> io::Result<String> {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            output.push_str(&rest[start..]);
+            return Ok(output);
+        };
+        let value = match &after[..end] {
+            "name" => shortcut.project_name.clone(),
+            "command" => command_line(&shortcut.run_command),
+            "description" => shortcut.description.clone().unwrap_or_default(),
+            "tags" => shortcut.tags.join(","),
+            "run_count" => shortcut.run_count.to_string(),
+            placeholder => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Unknown placeholder '{{{}}}'. Use {{name}}, {{command}}, {{description}}, {{tags}}, or {{run_count}}.",
+                        placeholder
+                    ),
+                ))
+            }
+        };
+        output.push_str(&value);
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Options that control which shortcuts `list_shortcuts` shows and how it prints them.
+#[derive(Default, Debug, Clone)]
+struct ListOptions {
+    /// Only list shortcuts carrying one of these tags. Empty means no filtering.
+    filter_tags: Vec<String>,
+    /// Require shortcuts to carry all of `filter_tags` instead of any one of them.
+    all_tags: bool,
+    /// Leave out the column headers of the table.
+    no_header: bool,
+    /// Print each shortcut as a multi-line block with all of its fields instead of as a table.
+    verbose: bool,
+    /// Print each shortcut on one short line instead of as a table.
+    compact: bool,
+    /// Print each shortcut with this template (see `format_shortcut`) instead of as a table.
+    format: Option<String>,
+    /// Print the shortcuts as a tree instead of as a table (see `print_shortcuts_tree`).
+    tree: bool,
+    /// Only list shortcuts in this namespace, e.g. `work` for `work/frontend`.
+    group: Option<String>,
+    /// The field to order the shortcuts by. `None` keeps the order they were added in.
+    sort_by: Option<SortField>,
+    /// Reverse the order of the shortcuts.
+    reverse: bool,
+    /// The output format selected with the global `--output-format` flag.
+    output_format: OutputFormat,
+}
+
+/// Groups shortcuts by their first tag.
+///
+/// Groups appear in the order their tag is first seen, and shortcuts keep their order within a
+/// group. Shortcuts without tags are collected in a group named `untagged`.
+fn group_shortcuts_by_tag(shortcuts: &[Shortcut]) -> IndexMap<&str, Vec<&Shortcut>> {
+    let mut groups: IndexMap<&str, Vec<&Shortcut>> = IndexMap::new();
+    for shortcut in shortcuts {
+        let tag = shortcut.tags.first().map_or("untagged", String::as_str);
+        groups.entry(tag).or_default().push(shortcut);
+    }
+    groups
+}
+
+/// Returns `true` if the shortcut named `name` is in the namespace `group`, e.g. `work/frontend`
+/// and `work/api/server` are in `work`, and `work/api/server` is also in `work/api`.
+fn in_namespace(name: &str, group: &str) -> bool {
+    let group = group.trim_end_matches('/');
+    name.get(..group.len())
+        .is_some_and(|prefix| names_match(prefix, group))
+        && name[group.len()..].starts_with('/')
+}
+
+/// A namespace or shortcut in the tree printed by `print_shortcuts_tree`.
+#[derive(Default)]
+struct NamespaceNode<'a> {
+    /// The shortcut whose name ends here, if any.
+    shortcut: Option<&'a Shortcut>,
+    /// The namespaces and shortcuts below this one, by the next part of their name.
+    children: IndexMap<&'a str, NamespaceNode<'a>>,
+}
+
+impl<'a> NamespaceNode<'a> {
+    /// Builds the tree of `shortcuts` by splitting their names at `/`.
+    fn build(shortcuts: &'a [Shortcut]) -> Self {
+        let mut root = NamespaceNode::default();
+        for shortcut in shortcuts {
+            let mut node = &mut root;
+            for part in shortcut.project_name.split('/') {
+                node = node.children.entry(part).or_default();
+            }
+            node.shortcut = Some(shortcut);
+        }
+        root
+    }
+
+    /// Writes the children of this node, one line each followed by their own children, with
+    /// every line starting with `prefix`.
+    fn write_children(&self, writer: &mut dyn Write, prefix: &str) -> io::Result<()> {
+        for (i, (part, child)) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+            let branch = if last { "└── " } else { "├── " };
+            write!(writer, "{}{}", prefix, branch)?;
+            child.write_line(writer, part)?;
+            child.write_children(
+                writer,
+                &format!("{}{}", prefix, if last { "    " } else { "│   " }),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the name of this node, followed by the command if it is a shortcut.
+    fn write_line(&self, writer: &mut dyn Write, part: &str) -> io::Result<()> {
+        match self.shortcut {
+            Some(shortcut) => writeln!(
+                writer,
+                "{}  {}",
+                highlight(part),
+                command_line(&shortcut.run_command)
+            ),
+            None => writeln!(writer, "{}", part),
+        }
+    }
+}
+
+/// Writes the shortcuts as a tree.
+///
+/// When any shortcut name contains a namespace (e.g., `work/frontend`), the tree follows the
+/// namespaces, with one level per part of the name. Otherwise there is one branch per group from
+/// `group_shortcuts_by_tag`.
+///
+/// # Errors
+/// This function will return an error if writing to `writer` fails.
+fn print_shortcuts_tree(shortcuts: &[Shortcut], writer: &mut dyn Write) -> io::Result<()> {
+    if shortcuts.iter().any(|s| s.project_name.contains('/')) {
+        let root = NamespaceNode::build(shortcuts);
+        for (part, node) in &root.children {
+            node.write_line(writer, part)?;
+            node.write_children(writer, "")?;
+        }
+        return Ok(());
+    }
+    for (tag, group) in group_shortcuts_by_tag(shortcuts) {
+        writeln!(writer, "{}", tag)?;
+        for (i, shortcut) in group.iter().enumerate() {
+            let branch = if i + 1 == group.len() {
+                "└──"
+            } else {
+                "├──"
+            };
+            writeln!(
+                writer,
+                "{} {}  {}",
+                branch,
+                highlight(&shortcut.project_name),
+                command_line(&shortcut.run_command)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the shortcuts that carry the given tags, in their original order.
+///
+/// A shortcut matches if it has at least one of `tags`, or with `all` if it has every one of them.
+/// When `tags` is empty, every shortcut matches.
+fn filter_shortcuts_by_tags<'a>(
+    shortcuts: &'a [Shortcut],
+    tags: &[String],
+    all: bool,
+) -> Vec<&'a Shortcut> {
+    shortcuts
+        .iter()
+        .filter(|s| {
+            if tags.is_empty() {
+                true
+            } else if all {
+                tags.iter().all(|tag| s.tags.contains(tag))
+            } else {
+                tags.iter().any(|tag| s.tags.contains(tag))
+            }
+        })
+        .collect()
+}
+
+/// A field that `list` can order shortcuts by.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum SortField {
+    /// Alphabetically by name
+    Name,
+    /// By number of runs, least-run first
+    RunCount,
+    /// By creation time, oldest first
+    #[value(alias = "created")]
+    CreatedAt,
+    /// By time of the last update, oldest first
+    #[value(alias = "updated")]
+    UpdatedAt,
+    /// By time of the last run, least recent first
+    #[value(alias = "last-run")]
+    LastRunAt,
+}
+
+/// Sorts shortcuts in ascending order of `field`.
+///
+/// Names are compared ignoring case unless `--case-sensitive` was passed. Shortcuts without a
+/// timestamp for the chosen field come first, and shortcuts with equal values keep their order.
+fn sort_shortcuts_by(shortcuts: &mut [Shortcut], field: SortField) {
+    match field {
+        SortField::Name if CASE_SENSITIVE.load(Ordering::Relaxed) => {
+            shortcuts.sort_by(|a, b| a.project_name.cmp(&b.project_name))
+        }
+        SortField::Name => shortcuts.sort_by_key(|s| s.project_name.to_lowercase()),
+        SortField::RunCount => shortcuts.sort_by_key(|s| s.run_count),
+        SortField::CreatedAt => shortcuts.sort_by(|a, b| a.created_at.cmp(&b.created_at)),
+        SortField::UpdatedAt => shortcuts.sort_by(|a, b| a.updated_at.cmp(&b.updated_at)),
+        SortField::LastRunAt => shortcuts.sort_by(|a, b| a.last_run_at.cmp(&b.last_run_at)),
+    }
+}
+
+/// The format used by `list` and `info` to print shortcuts.
+#[derive(ValueEnum, Default, Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// Pretty-printed JSON, with every field of each shortcut
+    Json,
+    /// Tab-separated values, one shortcut per line
+    Tsv,
+}
+
+/// Writes one shortcut as a line of tab-separated values: name, command, tags, description,
+/// working directory, environment variables (`KEY=VALUE` pairs separated by spaces), run count, and
+/// time of the last run.
+///
+/// Tabs and newlines inside the values are replaced by spaces so each shortcut stays on one line.
+fn write_tsv_line(shortcut: &Shortcut, writer: &mut dyn Write) -> io::Result<()> {
+    let fields = [
+        shortcut.project_name.clone(),
+        command_line(&shortcut.run_command),
+        shortcut.tags.join(","),
+        shortcut.description.clone().unwrap_or_default(),
+        shortcut
+            .working_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default(),
+        env_assignments(shortcut).join(" "),
+        shortcut.run_count.to_string(),
+        shortcut.last_run_at.clone().unwrap_or_default(),
+    ];
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| field.replace(['\t', '\n'], " "))
+        .collect();
+    writeln!(writer, "{}", fields.join("\t"))
+}
+
+/// Returns the width of the terminal in columns, if it can be determined.
+///
+/// The width is read from the terminal attached to stdout, falling back to the `COLUMNS`
+/// environment variable. Returns `None` if neither is available (e.g., when output is piped).
+fn terminal_width() -> Option<usize> {
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return Some(width as usize);
+    }
+    std::env::var("COLUMNS").ok()?.parse().ok()
+}
+
+/// Writes the given shortcuts as an aligned table.
+///
+/// The table has a column for the name and the command, plus columns for tags, description, and run
+/// count when any of the shortcuts has them. Locked shortcuts are marked with a lock icon. Commands are written the way they would be typed into a shell, and
+/// each line is cut off at the terminal width so long commands do not wrap.
+///
+/// # Arguments
+/// * `shortcuts` - The shortcuts to print.
+/// * `writer` - Where the table is written.
+/// * `show_header` - Whether to print a header line naming the columns.
+///
+/// # Errors
+/// This function will return an error if writing to `writer` fails.
+fn print_shortcuts_table(
+    shortcuts: &[Shortcut],
+    writer: &mut dyn Write,
+    show_header: bool,
+) -> io::Result<()> {
+    let show_tags = shortcuts.iter().any(|s| !s.tags.is_empty());
+    let show_description = shortcuts.iter().any(|s| s.description.is_some());
+    let show_runs = shortcuts.iter().any(|s| s.run_count > 0);
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    if show_header {
+        let mut header = vec!["NAME".to_string()];
+        if show_tags {
+            header.push("TAGS".to_string());
+        }
+        if show_description {
+            header.push("DESCRIPTION".to_string());
+        }
+        if show_runs {
+            header.push("RUNS".to_string());
+        }
+        header.push("COMMAND".to_string());
+        rows.push(header);
+    }
+    for shortcut in shortcuts {
+        let mut row = vec![if shortcut.locked {
+            format!("{} 🔒", shortcut.project_name)
+        } else {
+            shortcut.project_name.clone()
+        }];
+        if show_tags {
+            row.push(shortcut.tags.join(", "));
+        }
+        if show_description {
+            row.push(truncate(shortcut.description.as_deref().unwrap_or(""), 60));
+        }
+        if show_runs {
+            row.push(shortcut.run_count.to_string());
+        }
+        row.push(command_line(&shortcut.run_command));
+        rows.push(row);
+    }
+
+    // Every column but the last (the command) is padded to its widest value
+    let column_count = rows.first().map_or(0, |row| row.len());
+    let widths: Vec<usize> = (0..column_count)
+        .map(|i| rows.iter().map(|row| row[i].width()).max().unwrap_or(0))
+        .collect();
+    let max_width = terminal_width();
+
+    for (row_index, row) in rows.into_iter().enumerate() {
+        let mut line = String::new();
+        for (i, cell) in row.iter().enumerate() {
+            if i + 1 < column_count {
+                // Pad by display width, since the lock icon takes up two columns
+                let padding = " ".repeat(widths[i] - cell.width());
+                line.push_str(&format!("{}{} | ", cell, padding));
+            } else {
+                line.push_str(cell);
+            }
+        }
+        if let Some(max_width) = max_width {
+            line = truncate(&line, max_width);
+        }
+        // Color the name after padding and truncating so the escape codes don't skew the widths
+        let is_header = show_header && row_index == 0;
+        if let Some(rest) = line.strip_prefix(row[0].as_str()).filter(|_| !is_header) {
+            line = format!("{}{}", highlight(&row[0]), rest);
+        }
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Lists all the stored shortcuts and their associated commands.
+///
+/// This function loads the list of shortcuts from storage and prints them as a table of names,
+/// tags, descriptions, and commands (see `print_shortcuts_table`). With `options.verbose` or
+/// `options.compact`, each shortcut is instead printed as a block of lines or a single short line
+/// (see `ShortcutFormatter`), and with `options.tree` as a tree grouped by tag. If no shortcuts are
+/// found, a message indicating that no shortcuts are available is printed.
+///
+/// With the `json` output format, the shortcuts are written as a JSON array instead (an empty array
+/// if there are none), and with `tsv` as one line of tab-separated values per shortcut.
+///
+/// # Arguments
+/// * `options` - Which shortcuts to list and how to print them.
+///
+/// # Returns
+/// * `Ok(())` if the list of shortcuts is successfully retrieved and printed.
+/// * `Err(io::Error)` if an error occurs while loading the shortcuts.
+fn list_shortcuts(options: &ListOptions) -> io::Result<()> {
+    let all_shortcuts = load_visible_shortcuts()?;
+    let mut shortcuts: Vec<Shortcut> =
+        filter_shortcuts_by_tags(&all_shortcuts, &options.filter_tags, options.all_tags)
+            .into_iter()
+            .filter(|s| {
+                options
+                    .group
+                    .as_deref()
+                    .is_none_or(|group| in_namespace(&s.project_name, group))
+            })
+            .cloned()
+            .collect();
+    if let Some(field) = options.sort_by {
+        sort_shortcuts_by(&mut shortcuts, field);
+    }
+    if options.reverse {
+        shortcuts.reverse();
+    }
+
+    if porcelain() {
+        for shortcut in &shortcuts {
+            println!(
+                "{}\t{}",
+                shortcut.project_name,
+                porcelain_field(&command_line(&shortcut.run_command))
+            );
+        }
+        return Ok(());
+    }
+    match options.output_format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(io::stdout(), &shortcuts)?;
+            println!();
+            return Ok(());
+        }
+        OutputFormat::Tsv => {
+            for shortcut in &shortcuts {
+                write_tsv_line(shortcut, &mut io::stdout())?;
+            }
+            return Ok(());
+        }
+        OutputFormat::Text => {}
+    }
+
+    if let Some(template) = &options.format {
+        // Check the template even when there is nothing to list
+        format_shortcut(&Shortcut::default(), template)?;
+        for shortcut in &shortcuts {
+            println!("{}", format_shortcut(shortcut, template)?);
+        }
+    } else if shortcuts.is_empty() {
+        println!("No shortcuts found.");
+    } else if options.tree {
+        print_shortcuts_tree(&shortcuts, &mut io::stdout())?;
+    } else if options.verbose {
+        let formatter = ShortcutFormatter::default();
+        let blocks: Vec<String> = shortcuts.iter().map(|s| formatter.verbose(s)).collect();
+        print!("{}", blocks.join("\n"));
+    } else if options.compact {
+        let formatter = ShortcutFormatter::default();
+        for shortcut in &shortcuts {
+            println!("{}", formatter.compact(shortcut));
+        }
+    } else {
+        print_shortcuts_table(&shortcuts, &mut io::stdout(), !options.no_header)?;
+    }
+    Ok(())
+}
+
+/// Returns the `n` shortcuts that have been run the most, most-run first.
+///
+/// Shortcuts that have never been run are left out. Shortcuts run equally often keep their stored
+/// order.
+fn most_used_shortcuts(shortcuts: &[Shortcut], n: usize) -> Vec<&Shortcut> {
+    let mut used: Vec<&Shortcut> = shortcuts.iter().filter(|s| s.run_count > 0).collect();
+    used.sort_by_key(|s| std::cmp::Reverse(s.run_count));
+    used.truncate(n);
+    used
+}
+
+/// Returns the shortcut that was run most recently, or `None` if none has been run yet.
+fn last_run_shortcut(shortcuts: &[Shortcut]) -> Option<&Shortcut> {
+    // ISO-8601 UTC timestamps sort chronologically as strings
+    shortcuts
+        .iter()
+        .filter(|s| s.last_run_at.is_some())
+        .max_by(|a, b| a.last_run_at.cmp(&b.last_run_at))
+}
+
+/// Prints every field of a single shortcut.
+///
+/// In the default text format, each field is printed on its own labeled line, with one command
+/// token per line. The `json` format writes the whole shortcut as a JSON object and the `tsv` format
+/// as a single line of tab-separated values.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut to show.
+/// * `output_format` - The format to print the shortcut in.
+///
+/// # Returns
+/// * `Ok(())` if the shortcut was found and printed.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name`.
+/// * `Err(io::Error)` if an error occurs while loading the shortcuts.
+fn info_shortcut(name: &str, output_format: OutputFormat) -> io::Result<()> {
+    let shortcuts = load_visible_shortcuts()?;
+    let shortcut = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+
+    match output_format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(io::stdout(), shortcut)?;
+            println!();
+        }
+        OutputFormat::Tsv => write_tsv_line(shortcut, &mut io::stdout())?,
+        OutputFormat::Text => print!("{}", ShortcutFormatter::default().verbose(shortcut)),
+    }
+    Ok(())
+}
+
+/// A problem found by `validate_shortcuts` that would stop a shortcut from running as intended.
+#[derive(Debug, Clone, PartialEq)]
+enum ValidationError {
+    /// The shortcut has no command at all.
+    EmptyCommand { name: String },
+    /// The program the command starts is neither an existing path nor found in `PATH`.
+    CommandNotFound { name: String, program: String },
+    /// The working directory does not exist.
+    WorkingDirMissing { name: String, dir: PathBuf },
+    /// An environment variable holds an absolute path that does not exist.
+    EnvPathMissing {
+        name: String,
+        key: String,
+        path: PathBuf,
+    },
+}
+
+impl ValidationError {
+    /// Returns the name of the shortcut the problem was found in.
+    fn shortcut_name(&self) -> &str {
+        match self {
+            ValidationError::EmptyCommand { name }
+            | ValidationError::CommandNotFound { name, .. }
+            | ValidationError::WorkingDirMissing { name, .. }
+            | ValidationError::EnvPathMissing { name, .. } => name,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::EmptyCommand { .. } => write!(f, "the command is empty"),
+            ValidationError::CommandNotFound { program, .. } => {
+                write!(f, "'{}' was not found", program)
+            }
+            ValidationError::WorkingDirMissing { dir, .. } => {
+                write!(f, "working directory {:?} does not exist", dir)
+            }
+            ValidationError::EnvPathMissing { key, path, .. } => {
+                write!(f, "{} points to {:?}, which does not exist", key, path)
+            }
+        }
+    }
+}
+
+/// Returns `true` if `program` can be started: either it is a path to an existing file, or a file
+/// with that name exists in one of the directories in `PATH`, like the `which` command.
+fn program_exists(program: &str) -> bool {
+    if program.contains(std::path::MAIN_SEPARATOR) || program.contains('/') {
+        return Path::new(program).is_file();
+    }
+    let extensions: &[&str] = if cfg!(target_os = "windows") {
+        &["", ".exe", ".cmd", ".bat"]
+    } else {
+        &[""]
+    };
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| {
+            extensions
+                .iter()
+                .any(|extension| dir.join(format!("{}{}", program, extension)).is_file())
+        })
+    })
+}
+
+/// Checks every shortcut for problems that would stop it from running.
+///
+/// A shortcut is reported if its command is empty, the program it starts cannot be found, its
+/// working directory does not exist, or one of its environment variables holds an absolute path
+/// that does not exist. Environment variables are checked in order of their names.
+///
+/// # Returns
+/// All problems found, in the order of `shortcuts`. An empty vector means every shortcut is valid.
+fn validate_shortcuts(shortcuts: &[Shortcut]) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for shortcut in shortcuts {
+        let name = shortcut.project_name.clone();
+        match shortcut.run_command.first() {
+            None => errors.push(ValidationError::EmptyCommand { name: name.clone() }),
+            Some(program) if !program_exists(program) => {
+                errors.push(ValidationError::CommandNotFound {
+                    name: name.clone(),
+                    program: program.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+        if let Some(dir) = &shortcut.working_dir {
+            if !dir.is_dir() {
+                errors.push(ValidationError::WorkingDirMissing {
+                    name: name.clone(),
+                    dir: dir.clone(),
+                });
+            }
+        }
+        let mut env: Vec<(&String, &String)> = shortcut.env.iter().collect();
+        env.sort();
+        for (key, value) in env {
+            let path = Path::new(value);
+            if path.is_absolute() && !path.exists() {
+                errors.push(ValidationError::EnvPathMissing {
+                    name: name.clone(),
+                    key: key.clone(),
+                    path: path.to_path_buf(),
+                });
+            }
+        }
+    }
+    errors
+}
+
+/// How serious a problem found by `run_doctor` is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiagnosticStatus {
+    /// Nothing is wrong.
+    Ok,
+    /// Something may not work, e.g. an optional tool is missing.
+    Warn,
+    /// Something is broken and needs fixing.
+    Error,
+}
+
+impl std::fmt::Display for DiagnosticStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticStatus::Ok => write!(f, "ok"),
+            DiagnosticStatus::Warn => write!(f, "warn"),
+            DiagnosticStatus::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The result of one check made by `run_doctor`.
+#[derive(Debug, Clone, PartialEq)]
+struct DiagnosticResult {
+    /// What was checked, e.g. "Config file".
+    check: String,
+    /// Whether the check passed.
+    status: DiagnosticStatus,
+    /// What was found.
+    detail: String,
+}
+
+impl DiagnosticResult {
+    fn new(check: &str, status: DiagnosticStatus, detail: impl Into<String>) -> Self {
+        DiagnosticResult {
+            check: check.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Checks the shortcuts themselves for problems that stop commands from finding or running them:
+/// empty commands, and names used by more than one shortcut (see `names_match`).
+fn diagnose_shortcuts(shortcuts: &[Shortcut]) -> Vec<DiagnosticResult> {
+    let empty: Vec<&str> = shortcuts
+        .iter()
+        .filter(|s| s.run_command.is_empty())
+        .map(|s| s.project_name.as_str())
+        .collect();
+    let duplicates: Vec<&str> = shortcuts
+        .iter()
+        .enumerate()
+        .filter(|(i, s)| {
+            shortcuts[..*i]
+                .iter()
+                .any(|earlier| names_match(&earlier.project_name, &s.project_name))
+        })
+        .map(|(_, s)| s.project_name.as_str())
+        .collect();
+
+    let result = |check: &str, names: &[&str]| {
+        if names.is_empty() {
+            DiagnosticResult::new(check, DiagnosticStatus::Ok, "none")
+        } else {
+            DiagnosticResult::new(check, DiagnosticStatus::Error, names.join(", "))
+        }
+    };
+    vec![
+        result("Empty commands", &empty),
+        result("Duplicate names", &duplicates),
+    ]
+}
+
+/// Diagnoses common problems with the environment and the configuration.
+///
+/// Checks that the configuration file can be read and parsed, that its directory is writable,
+/// that `git` and the program used to open folders (`xdg-open`, `open`, or `explorer`) are
+/// installed, and that no shortcut has an empty command or shares its name with another one.
+/// Nothing is created or changed.
+///
+/// # Returns
+/// One result per check, in that order.
+fn run_doctor() -> Vec<DiagnosticResult> {
+    let mut results = Vec::new();
+    let path = config_file_path();
+
+    let shortcuts = match fs::read_to_string(&path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            results.push(DiagnosticResult::new(
+                "Config file",
+                DiagnosticStatus::Warn,
+                format!("{} does not exist yet", path.display()),
+            ));
+            Some(Vec::new())
+        }
+        Err(e) => {
+            results.push(DiagnosticResult::new(
+                "Config file",
+                DiagnosticStatus::Error,
+                format!("cannot read {}: {}", path.display(), e),
+            ));
+            None
+        }
+        Ok(data) => {
+            let parsed = match config_format() {
+                ConfigFormat::Json => parse_config(&data),
+                ConfigFormat::Toml => parse_toml_config(&data),
+            };
+            match parsed {
+                Ok(shortcuts) => {
+                    results.push(DiagnosticResult::new(
+                        "Config file",
+                        DiagnosticStatus::Ok,
+                        format!("{} ({} shortcuts)", path.display(), shortcuts.len()),
+                    ));
+                    Some(shortcuts)
+                }
+                Err(e) => {
+                    results.push(DiagnosticResult::new(
+                        "Config file",
+                        DiagnosticStatus::Error,
+                        format!("{} is invalid: {}", path.display(), e),
+                    ));
+                    None
+                }
+            }
+        }
+    };
+
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    results.push(if !dir.is_dir() {
+        DiagnosticResult::new(
+            "Config directory",
+            DiagnosticStatus::Warn,
+            format!("{} does not exist yet", dir.display()),
+        )
+    } else {
+        match tempfile::NamedTempFile::new_in(&dir) {
+            Ok(_) => DiagnosticResult::new(
+                "Config directory",
+                DiagnosticStatus::Ok,
+                format!("{} is writable", dir.display()),
+            ),
+            Err(e) => DiagnosticResult::new(
+                "Config directory",
+                DiagnosticStatus::Error,
+                format!("cannot write to {}: {}", dir.display(), e),
+            ),
+        }
+    });
+
+    let opener = open_command(None)
+        .ok()
+        .and_then(|words| words.first().cloned());
+    for (check, program, missing) in [
+        ("git", Some("git".to_string()), "git commands will not work"),
+        ("File opener", opener, "open commands will not work"),
+    ] {
+        results.push(match program {
+            Some(program) if program_exists(&program) => {
+                DiagnosticResult::new(check, DiagnosticStatus::Ok, format!("{} found", program))
+            }
+            Some(program) => DiagnosticResult::new(
+                check,
+                DiagnosticStatus::Warn,
+                format!("{} not found in PATH; {}", program, missing),
+            ),
+            None => DiagnosticResult::new(
+                check,
+                DiagnosticStatus::Warn,
+                "not supported on this operating system",
+            ),
+        });
+    }
+
+    match shortcuts {
+        Some(shortcuts) => results.extend(diagnose_shortcuts(&shortcuts)),
+        None => {
+            for check in ["Empty commands", "Duplicate names"] {
+                results.push(DiagnosticResult::new(
+                    check,
+                    DiagnosticStatus::Warn,
+                    "skipped, the config file could not be read",
+                ));
+            }
+        }
+    }
+    results
+}
+
+/// Writes the results of `run_doctor` as an aligned table with the columns CHECK, STATUS and
+/// DETAIL, coloring each status.
+fn print_diagnostics(results: &[DiagnosticResult], writer: &mut dyn Write) -> io::Result<()> {
+    let width = results
+        .iter()
+        .map(|r| r.check.chars().count())
+        .chain(["CHECK".len()])
+        .max()
+        .unwrap_or(0);
+    writeln!(
+        writer,
+        "{:<width$} | STATUS | DETAIL",
+        "CHECK",
+        width = width
+    )?;
+    for result in results {
+        // Pad before coloring so the escape codes don't count towards the width
+        let status = format!("{:<6}", result.status.to_string());
+        let status = match result.status {
+            DiagnosticStatus::Ok => success(&status),
+            DiagnosticStatus::Warn => paint(&status, Color::Yellow, false),
+            DiagnosticStatus::Error => paint(&status, Color::Red, false),
+        };
+        writeln!(
+            writer,
+            "{:<width$} | {} | {}",
+            result.check,
+            status,
+            result.detail,
+            width = width
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the first token of a command that looks like a filesystem path, if any.
+///
+/// A token looks like a path if it is absolute or contains a path separator, which is how `add`
+/// stores paths that existed when the shortcut was created.
+fn first_path_token(command: &[String]) -> Option<&Path> {
+    command
+        .iter()
+        .map(Path::new)
+        .find(|path| path.is_absolute() || path.components().count() > 1)
+}
+
+/// Removes shortcuts whose project no longer exists on disk.
+///
+/// A shortcut is pruned if the first path-like token of its command (see `first_path_token`) does
+/// not exist, for example because the project was deleted or moved. Shortcuts without any path in
+/// their command, such as `cargo build`, are always kept. Each pruned shortcut is printed.
+///
+/// # Arguments
+/// * `dry_run` - Only print which shortcuts would be removed, without changing anything.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were (or, with `dry_run`, would be) removed.
+/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts.
+fn prune_shortcuts(dry_run: bool) -> io::Result<usize> {
+    let _lock = lock_config()?;
+    let shortcuts = load_shortcuts()?;
+    let (stale, kept): (Vec<Shortcut>, Vec<Shortcut>) =
+        shortcuts.into_iter().partition(|shortcut| {
+            first_path_token(&shortcut.run_command).is_some_and(|path| !path.exists())
+        });
+    for shortcut in &stale {
+        let path = first_path_token(&shortcut.run_command).unwrap_or(Path::new(""));
+        println!(
+            "{} {} ({:?} does not exist)",
+            if dry_run { "Would remove" } else { "Removing" },
+            highlight(&shortcut.project_name),
+            path
+        );
+    }
+    if !dry_run && !stale.is_empty() {
+        save_shortcuts(&kept)?;
+    }
+    Ok(stale.len())
+}
+
+/// Returns the names of all stored shortcuts, in storage order.
+///
+/// # Errors
+/// This function will return an error if the shortcuts cannot be loaded.
+fn list_names() -> io::Result<Vec<String>> {
+    // Completion scripts call this on every key press, so never create the config file here
+    if !config_file_path().exists() {
+        return Ok(Vec::new());
+    }
+    Ok(load_visible_shortcuts()?
+        .into_iter()
+        .map(|shortcut| shortcut.project_name)
+        .collect())
+}
+
+/// Computes the Levenshtein edit distance between two strings.
+///
+/// The distance is the minimum number of single-character insertions, deletions, or substitutions
+/// needed to turn `a` into `b`. Characters are compared as Unicode scalar values.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+            current[j + 1] = (previous[j] + substitution_cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b_chars.len()]
+}
+
+/// "Did you mean ...?" suggestions for mistyped shortcut names.
+mod suggestion {
+    use super::{levenshtein, CASE_SENSITIVE};
+    use std::sync::atomic::Ordering;
+
+    /// The largest edit distance at which a name is still suggested.
+    const MAX_DISTANCE: usize = 3;
+
+    /// Returns the name closest to `query` by Levenshtein distance, if any is at most
+    /// `MAX_DISTANCE` edits away.
+    ///
+    /// Case is ignored unless `--case-sensitive` was passed. When several names are equally close,
+    /// the first one wins.
+    ///
+    /// # Arguments
+    /// * `query` - The name that was not found.
+    /// * `names` - The names to pick a suggestion from.
+    pub(crate) fn closest_match<'a>(
+        query: &str,
+        names: impl Iterator<Item = &'a str>,
+    ) -> Option<&'a str> {
+        let case_sensitive = CASE_SENSITIVE.load(Ordering::Relaxed);
+        let query = if case_sensitive {
+            query.to_string()
+        } else {
+            query.to_lowercase()
+        };
+        names
+            .map(|name| {
+                let distance = if case_sensitive {
+                    levenshtein(&query, name)
+                } else {
+                    levenshtein(&query, &name.to_lowercase())
+                };
+                (distance, name)
+            })
+            .filter(|(distance, _)| *distance <= MAX_DISTANCE)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name)
+    }
+}
+
+/// Ranks shortcuts by how closely their names match a search query.
+///
+/// Each shortcut is scored by the Levenshtein distance between `query` and its `project_name`
+/// (ignoring case unless `--case-sensitive` was passed). Shortcuts further than `max_distance` edits
+/// away are dropped, and the rest are sorted by ascending score so an exact match always comes first.
+///
+/// # Arguments
+/// * `shortcuts` - The shortcuts to search through.
+/// * `query` - The (possibly misspelled) name to look for.
+/// * `max_distance` - The maximum number of edits for a shortcut to count as a match.
+///
+/// # Returns
+/// The matching shortcuts paired with their distance, best match first.
+fn search_shortcuts<'a>(
+    shortcuts: &'a [Shortcut],
+    query: &str,
+    max_distance: usize,
+) -> Vec<(&'a Shortcut, usize)> {
+    let mut results: Vec<(&Shortcut, usize)> = shortcuts
+        .iter()
+        .map(|shortcut| {
+            let distance = if CASE_SENSITIVE.load(Ordering::Relaxed) {
+                levenshtein(query, &shortcut.project_name)
+            } else {
+                levenshtein(&query.to_lowercase(), &shortcut.project_name.to_lowercase())
+            };
+            (shortcut, distance)
+        })
+        .filter(|(_, distance)| *distance <= max_distance)
+        .collect();
+
+    // A stable sort keeps shortcuts with equal scores in their stored order
+    results.sort_by_key(|(_, distance)| *distance);
+    results
+}
+
+/// Prints the shortcuts whose names are close to the given query.
+///
+/// This function loads the stored shortcuts, ranks them with `search_shortcuts`, and prints each match
+/// together with its score (the number of edits away from `query`). If nothing is within
+/// `max_distance` edits, a message saying so is printed instead.
+///
+/// # Arguments
+/// * `query` - The name to search for.
+/// * `max_distance` - The maximum number of edits for a shortcut to count as a match.
+///
+/// # Returns
+/// * `Ok(())` if the search completed, whether or not anything matched.
+/// * `Err(io::Error)` if an error occurs while loading the shortcuts.
+fn print_search_results(query: &str, max_distance: usize) -> io::Result<()> {
+    let shortcuts = load_visible_shortcuts()?;
+    let results = search_shortcuts(&shortcuts, query, max_distance);
+
+    if results.is_empty() {
+        println!("No shortcuts found matching '{}'.", query);
+    } else {
+        for (shortcut, distance) in results {
+            println!(
+                "{} (score {}): {:?}",
+                highlight(&shortcut.project_name),
+                distance,
+                shortcut.run_command
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the project directory of a shortcut.
+///
+/// This is the shortcut's working directory if it has one. Otherwise it is derived from the first
+/// element of `run_command`: that path itself if it is a directory, or its parent directory if it is
+/// a file such as an executable or script.
+///
+/// # Errors
+/// * `io::ErrorKind::InvalidData` if the shortcut's run command is empty.
+/// * `io::ErrorKind::NotFound` if no directory can be derived from the run command.
+fn resolve_project_dir(shortcut: &Shortcut) -> io::Result<PathBuf> {
+    if let Some(dir) = &shortcut.working_dir {
+        return Ok(dir.clone());
+    }
+    let first_command = shortcut.run_command.first().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Run command is empty for shortcut '{}'",
+                shortcut.project_name
+            ),
+        )
+    })?;
+    let path = Path::new(first_command);
+    if path.is_dir() {
+        Ok(path.to_path_buf())
+    } else if let Some(parent) = path.parent() {
+        Ok(parent.to_path_buf())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "Unable to determine directory from run command",
+        ))
+    }
+}
+
+/// Returns the command, without the folder or file argument, that opens a folder or file in `app`
+/// or, when `app` is `None`, with the system default (the file manager for folders).
+///
+/// An application is opened with `open -a <app>` on macOS and `start "" <app>` on Windows. On Linux
+/// `app` is the command itself (e.g., `code` or `idea`), which may include arguments.
+///
+/// # Errors
+/// Returns `io::ErrorKind::Unsupported` on operating systems other than Windows, macOS, and Linux,
+/// and `io::ErrorKind::InvalidInput` if `app` is blank on Linux.
+fn open_command(app: Option<&str>) -> io::Result<Vec<String>> {
+    let words: Vec<&str> = if cfg!(target_os = "windows") {
+        match app {
+            Some(app) => vec!["cmd", "/C", "start", "", app],
+            None => vec!["explorer"],
+        }
+    } else if cfg!(target_os = "macos") {
+        match app {
+            Some(app) => vec!["open", "-a", app],
+            None => vec!["open"],
+        }
+    } else if cfg!(target_os = "linux") {
+        match app {
+            Some(app) => {
+                let words = split_shell_words(app);
+                if words.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "The application cannot be empty",
+                    ));
+                }
+                return Ok(words);
+            }
+            None => vec!["xdg-open"],
+        }
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Unsupported operating system",
+        ));
+    };
+    Ok(words.into_iter().map(String::from).collect())
+}
+
+/// Opens the project folder associated with the given shortcut name.
+///
+/// This function searches for a shortcut with the specified name, retrieves the first command
+/// from the shortcut's `run_command` (assumed to be the project folder path), and opens that folder
+/// using the appropriate system file manager, or in `app` if one is given (see `open_command`).
+///
+/// # Arguments
+/// * `name` - The name of the project whose folder is to be opened.
+/// * `app` - The application to open the folder in, such as an IDE, instead of the file manager.
+///
+/// # Returns
+/// * `Ok(())` if the folder is successfully opened.
+/// * `Err(io::Error)` if an error occurs while retrieving the shortcut or opening the folder.
+///
+/// # Errors
+/// The function will return an error if:
+/// - No shortcut with the given name is found (`io::ErrorKind::NotFound`).
+/// - The `run_command` for the shortcut is empty (`io::ErrorKind::InvalidData`).
+/// - The folder path is invalid or cannot be determined from the run command.
+/// - The operating system is unsupported (other than Windows, macOS, or Linux).
+fn open_project_folder(name: &str, app: Option<&str>) -> io::Result<()> {
+    let shortcuts = load_visible_shortcuts()?;
+    let shortcut = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+    log!("Opening project folder for: {:?}", shortcut.project_name);
+    let dir = resolve_project_dir(shortcut)?;
+
+    // Open the directory using system file manager or the requested application
+    let open_command = open_command(app)?;
+    Command::new(&open_command[0])
+        .args(&open_command[1..])
+        .arg(&dir)
+        .spawn()?
+        .wait()?; // Wait for the command to complete
+    Ok(())
+}
+
+/// Opens a terminal emulator in the directory of a project.
+///
+/// The directory is found the same way as for `open_project_folder`. The default terminal is
+/// `gnome-terminal` on Linux, Terminal.app on macOS, and `cmd.exe` on Windows; `app` names another
+/// one, as a command on Linux (e.g., `alacritty`) and as an application name on macOS (e.g.,
+/// `iTerm`). The terminal is started in the background and keeps running after projexts exits.
+///
+/// # Arguments
+/// * `name` - The name of the project to open a terminal for.
+/// * `app` - The terminal emulator to use instead of the default one.
+///
+/// # Errors
+/// Returns an error if no shortcut is named `name`, its directory cannot be determined, the
+/// operating system is unsupported, or the terminal cannot be started.
+fn open_terminal(name: &str, app: Option<&str>) -> io::Result<()> {
+    let shortcuts = load_visible_shortcuts()?;
+    let shortcut = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+    let dir = resolve_project_dir(shortcut)?;
+
+    let mut words: Vec<String> = if cfg!(target_os = "windows") {
+        let app = app.unwrap_or("cmd.exe");
+        ["cmd", "/C", "start", "", app].map(String::from).to_vec()
+    } else if cfg!(target_os = "macos") {
+        let app = app.unwrap_or("Terminal");
+        // Terminal.app ignores the working directory of `open`, so the folder is passed instead
+        ["open", "-a", app].map(String::from).to_vec()
+    } else if cfg!(target_os = "linux") {
+        split_shell_words(app.unwrap_or("gnome-terminal"))
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Unsupported operating system",
+        ));
+    };
+    if cfg!(target_os = "macos") {
+        words.push(dir.to_string_lossy().to_string());
+    }
+    let (program, args) = words.split_first().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The terminal application cannot be empty",
+        )
+    })?;
+    log!("Opening a terminal in {}", dir.display());
+    Command::new(program).args(args).current_dir(&dir).spawn()?;
+    Ok(())
+}
+
+/// Replaces `$VAR` and `${VAR}` in `token` with the values of those environment variables.
+///
+/// Names are made of letters, digits, and `_`, and don't start with a digit. References to
+/// variables that are not set, and a `$` not followed by a name, are kept as written.
+fn expand_env_vars(token: &str) -> String {
+    let mut result = String::with_capacity(token.len());
+    let mut rest = token;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+        let valid = !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        match std::env::var(name) {
+            Ok(value) if valid => result.push_str(&value),
+            _ => result.push_str(&rest[start..start + 1 + consumed]),
+        }
+        rest = &after[consumed..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Replaces a leading `~` in `s` with the home directory, as a shell would.
+///
+/// Only `~` on its own or followed by `/` is expanded; `~user` forms and a `~` anywhere else are
+/// left as they are, as is everything if the home directory is unknown.
+fn expand_tilde(s: &str) -> String {
+    let rest = match s.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+        _ => return s.to_string(),
+    };
+    match dirs::home_dir() {
+        Some(home) => format!("{}{}", home.to_string_lossy(), rest),
+        None => s.to_string(),
+    }
+}
+
+/// Returns `token` as an absolute path if it is a relative path to an existing file or directory,
+/// and unchanged otherwise. A leading `~` is expanded first (see `expand_tilde`), so `~/bin/tool`
+/// becomes an absolute path even if it does not exist.
+fn absolute_token(token: &str) -> String {
+    let token = expand_tilde(token);
+    let path = Path::new(&token);
+    if path.is_absolute() {
+        token
+    } else if let Ok(abs_path) = fs::canonicalize(path) {
+        abs_path.to_string_lossy().to_string()
+    } else {
+        token
+    }
+}
+
+/// Builds the process that runs `command` with `args` in the working directory and environment of
+/// `shortcut`.
+///
+/// Environment variable references in the command are expanded here (see `expand_env_vars`), so
+/// the stored command keeps them unexpanded. For shortcuts with `ResolveMode::Lazy`, relative paths
+/// are made absolute here as well.
+fn build_process(shortcut: &Shortcut, command: &str, args: &[String]) -> Command {
+    let resolve = |token: &str| {
+        let token = expand_env_vars(token);
+        match shortcut.resolve {
+            ResolveMode::Lazy => absolute_token(&token),
+            ResolveMode::Eager => token,
+        }
+    };
+    let mut process = Command::new(resolve(command));
+    process.args(args.iter().map(|arg| resolve(arg)));
+    if let Some(dir) = &shortcut.working_dir {
+        process.current_dir(dir);
+    }
+    process.envs(&shortcut.env);
+    process
+}
+
+/// Limits the environment of `process` to the shortcut's own variables, plus those named in
+/// `options.inherit_only`, if `options.no_inherit_env` is set. Otherwise leaves it unchanged.
+fn restrict_env(process: &mut Command, shortcut: &Shortcut, options: &RunOptions) {
+    if !options.no_inherit_env {
+        return;
+    }
+    process.env_clear();
+    for key in &options.inherit_only {
+        if let Some(value) = std::env::var_os(key) {
+            process.env(key, value);
+        }
+    }
+    process.envs(&shortcut.env);
+}
+
+/// Copies a command to the system clipboard as a single shell-quoted line.
+///
+/// On Linux, the clipboard belongs to the program that set it and is emptied when that program
+/// exits, so the contents are handed to `wl-copy`, `xclip`, or `xsel` when one of them is installed;
+/// these keep running in the background until something else is copied. Elsewhere, and when none of
+/// them is available, the `arboard` crate is used.
+///
+/// # Arguments
+/// * `command` - The command and its arguments to copy.
+///
+/// # Errors
+/// Returns an error if no clipboard is available, e.g. when running without a graphical session.
+fn copy_to_clipboard(command: &[String]) -> io::Result<()> {
+    let text = command_line(command);
+    if cfg!(target_os = "linux") {
+        let tools: [&[&str]; 3] = [
+            &["wl-copy"],
+            &["xclip", "-selection", "clipboard"],
+            &["xsel", "--clipboard", "--input"],
+        ];
+        for tool in tools {
+            let child = Command::new(tool[0])
+                .args(&tool[1..])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            // Not installed, try the next one
+            let Ok(mut child) = child else { continue };
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(text.as_bytes())?;
+            }
+            if child.wait()?.success() {
+                return Ok(());
+            }
+        }
+    }
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| io::Error::other(format!("Clipboard is not available: {}", e)))
+}
+
+/// Options that control how `run_shortcut` runs a command.
+#[derive(Default, Debug, Clone)]
+struct RunOptions {
+    /// Stop the command if it is still running after this long.
+    timeout: Option<Duration>,
+    /// Start the command detached from the terminal and return without waiting for it.
+    background: bool,
+    /// Print the fully resolved command instead of running it.
+    dry_run: bool,
+    /// Values for `{NAME}` placeholders in the command, by name.
+    set_vars: HashMap<String, String>,
+    /// A file to append the command's output to, in addition to showing it.
+    log: Option<PathBuf>,
+    /// Print how long the command took once it has finished.
+    measure_time: bool,
+    /// Start the command with only the shortcut's own environment variables.
+    no_inherit_env: bool,
+    /// Variables passed on from projexts' environment despite `no_inherit_env`.
+    inherit_only: Vec<String>,
+    /// How many more times to start the command if it exits unsuccessfully.
+    retries: u32,
+    /// How long to wait before each retry.
+    retry_delay: Duration,
+    /// Connect the command's output to a pseudo-terminal instead of directly to ours.
+    allocate_pty: bool,
+}
+
+/// Formats how long a command ran for `run --measure-time`, e.g. `Command finished in 3.142s`.
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("Command finished in {:.3}s", elapsed.as_secs_f64())
+}
+
+/// Configures a command to keep running on its own after projexts exits.
+///
+/// All standard streams are redirected to the null device so the command never touches the
+/// terminal, and on Unix it is moved into its own process group so signals sent to the terminal's
+/// foreground group (such as Ctrl+C) do not reach it.
+fn detach(command: &mut Command) {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+}
+
+/// How long a command gets to exit after being asked to terminate before it is killed.
+const TERMINATE_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Asks a child process to terminate: `SIGTERM` on Unix, `TerminateProcess` elsewhere.
+fn terminate_child(child: &mut Child) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        // SAFETY: `kill` has no memory-safety preconditions; the PID belongs to our own child
+        if unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        child.kill()
+    }
+}
+
+/// Connects the stdout and stderr of `process` to a new pseudo-terminal with the size of ours, so
+/// the command sees a terminal (and keeps its colors and progress bars) even though projexts
+/// relays its output. Stdin stays connected to our own stdin, so interactive input still works.
+///
+/// # Returns
+/// The controlling side of the pseudo-terminal, from which the command's output is read.
+///
+/// # Errors
+/// Returns an error if no pseudo-terminal can be opened, and `io::ErrorKind::Unsupported` on
+/// platforms other than Unix.
+fn attach_pty(process: &mut Command) -> io::Result<fs::File> {
+    #[cfg(unix)]
+    {
+        let size = terminal_size::terminal_size().map(|(width, height)| nix::pty::Winsize {
+            ws_row: height.0,
+            ws_col: width.0,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        });
+        let pty = nix::pty::openpty(size.as_ref(), None)?;
+        process
+            .stdout(Stdio::from(pty.slave.try_clone()?))
+            .stderr(Stdio::from(pty.slave));
+        Ok(fs::File::from(pty.master))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = process;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "--allocate-pty is only supported on Unix",
+        ))
+    }
+}
+
+/// Copies everything read from the controlling side of a pseudo-terminal to stdout on a new
+/// thread, until the command and anything it started have closed the terminal.
+fn relay_pty_output(mut master: fs::File) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut stdout = io::stdout();
+        let mut buffer = [0; 4096];
+        // Once the other side is closed, reading fails (with EIO on Linux) or returns 0
+        while let Ok(count) = io::Read::read(&mut master, &mut buffer) {
+            if count == 0 || stdout.write_all(&buffer[..count]).is_err() {
+                break;
+            }
+            let _ = stdout.flush();
+        }
+    })
+}
+
+/// Waits for a child process to exit, stopping it if it runs longer than `timeout`.
+///
+/// Without a timeout this simply waits for the child. With one, a child still running once the
+/// timeout has elapsed is asked to terminate, given `TERMINATE_GRACE_PERIOD` to exit, and then killed.
+///
+/// # Returns
+/// * `Ok(status)` with the exit status if the child exited in time.
+/// * `Err(io::Error)` with `io::ErrorKind::TimedOut` if the child had to be stopped.
+/// * `Err(io::Error)` if waiting on or signalling the child fails.
+fn wait_with_timeout(child: &mut Child, timeout: Option<Duration>) -> io::Result<ExitStatus> {
+    let Some(timeout) = timeout else {
+        return child.wait();
+    };
+    if let Some(status) = child.wait_timeout(timeout)? {
+        return Ok(status);
+    }
+
+    terminate_child(child)?;
+    if child.wait_timeout(TERMINATE_GRACE_PERIOD)?.is_none() {
+        child.kill()?;
+        child.wait()?;
+    }
+    Err(io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!(
+            "Command did not finish within {} seconds and was stopped",
+            timeout.as_secs()
+        ),
+    ))
+}
+
+/// Runs the command associated with a given shortcut, with optional additional arguments.
+///
+/// This function searches for a shortcut by name, retrieves the associated command, and runs it with
+/// the stored arguments combined with any additional arguments provided by the user. The command is
+/// executed and the function waits for it to complete before returning. If `options.timeout` is set
+/// and the command runs longer, it is stopped (see `wait_with_timeout`). With `options.background`,
+/// the command is detached from the terminal instead and its PID is printed without waiting for it.
+/// With `options.dry_run`, nothing is run; the fully resolved command is printed in a form that can
+/// be pasted into a shell, without asking for `{NAME}` placeholders that have no `--set` value.
+///
+/// # Arguments
+/// * `name` - The name of the project whose associated command is to be run.
+/// * `extra_args` - A vector of extra arguments to append to the command’s stored arguments.
+/// * `options` - Additional options controlling how the command is run.
+///
+/// # Returns
+/// * `Ok(status)` with the exit status of the command once it has finished. A command started in
+///   the background or only printed with `dry_run` is reported as successful.
+/// * `Err(io::Error)` if an error occurs while retrieving the shortcut or running the command.
+///
+/// # Errors
+/// The function will return an error if:
+/// - No shortcut with the given name is found.
+/// - The `run_command` for the shortcut is empty.
+/// - An error occurs when trying to spawn or wait for the command to finish.
+/// - The command runs longer than the timeout.
+fn run_shortcut(
+    name: &str,
+    extra_args: Vec<String>,
+    options: &RunOptions,
+) -> io::Result<ExitStatus> {
+    debug_log!(
+        "run_shortcut(name: {:?}, extra_args: {:?}, options: {:?})",
+        name,
+        extra_args,
+        options
+    );
+    let shortcuts = load_visible_shortcuts()?;
+    let shortcut = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+    debug_log!("run_shortcut: found {:#?}", shortcut);
+    // Combine stored args with extra args
+    let full_command: Vec<String> = shortcut
+        .run_command
+        .iter()
+        .cloned()
+        .chain(extra_args)
+        .collect();
+    let full_command = if options.dry_run {
+        // A dry run never prompts; placeholders without a `--set` value are printed as they are
+        substitute_placeholders(&full_command, |name| {
+            Ok(options
+                .set_vars
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| format!("{{{}}}", name)))
+        })?
+    } else {
+        resolve_template_args(&full_command, &options.set_vars)?
+    };
+    debug_log!("run_shortcut: full command {:?}", full_command);
+    let (command, args) = full_command.split_first().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Command for '{}' is empty", name),
+        )
+    })?;
+    if options.dry_run {
+        let mut line = env_assignments(shortcut);
+        line.push(command_line(&full_command));
+        let line = line.join(" ");
+        match &shortcut.working_dir {
+            Some(dir) => println!("cd {} && {}", shell_quote(&dir.to_string_lossy()), line),
+            None => println!("{}", line),
+        }
+        return Ok(ExitStatus::default());
+    }
+    log!("Running command: {:?}", shortcut.run_command);
+
+    if let Some(pre_run) = &shortcut.pre_run {
+        run_hook(shortcut, pre_run, "pre-run", options)?;
+    }
+    let mut process = build_process(shortcut, command, args);
+    restrict_env(&mut process, shortcut, options);
+    if options.background {
+        detach(&mut process);
+        let child = process.spawn()?;
+        note_run(name);
+        if porcelain() {
+            println!("pid\t{}", child.id());
+        }
+        log!(
+            "Started '{}' in the background with PID {}",
+            name,
+            child.id()
+        );
+        return Ok(ExitStatus::default());
+    }
+
+    let log = options
+        .log
+        .as_deref()
+        .map(|path| fs::OpenOptions::new().create(true).append(true).open(path))
+        .transpose()?;
+    if log.is_some() {
+        process.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+    let started = std::time::Instant::now();
+    let mut retry = 0;
+    let result = loop {
+        let pty = if options.allocate_pty {
+            Some(attach_pty(&mut process)?)
+        } else {
+            None
+        };
+        let spawned = process.spawn();
+        let relay = pty.map(|master| {
+            // Close our copies of the command's side of the terminal, so relaying stops once the
+            // command exits
+            process.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+            relay_pty_output(master)
+        });
+        let result = spawned.and_then(|mut child| {
+            if porcelain() {
+                println!("pid\t{}", child.id());
+            }
+            // Retries are part of the same run
+            if retry == 0 {
+                note_run(name);
+            }
+            // Wait for the command to complete
+            match &log {
+                Some(log) => wait_with_log(&mut child, options.timeout, log),
+                None => wait_with_timeout(&mut child, options.timeout),
+            }
+        });
+        if let Some(relay) = relay {
+            let _ = relay.join();
+        }
+        match &result {
+            Ok(status) if !status.success() && retry < options.retries => {
+                retry += 1;
+                eprintln!(
+                    "{}",
+                    warning(&format!(
+                        "Command exited with {}; retry {}/{} in {}s",
+                        status,
+                        retry,
+                        options.retries,
+                        options.retry_delay.as_secs()
+                    ))
+                );
+                std::thread::sleep(options.retry_delay);
+            }
+            _ => break result,
+        }
+    };
+    if options.measure_time {
+        eprintln!("{}", format_elapsed(started.elapsed()));
+    }
+    if let Some(post_run) = &shortcut.post_run {
+        if let Err(e) = run_hook(shortcut, post_run, "post-run", options) {
+            eprintln!("{}", warning(&format!("Warning: {}", e)));
+        }
+    }
+    result
+}
+
+/// Copies `reader` line by line to `terminal` and, prefixed with the current UTC time, to `log`.
+///
+/// # Errors
+/// Returns an error if reading or writing fails.
+fn tee_lines(
+    reader: impl io::Read,
+    mut terminal: impl Write,
+    log: &std::sync::Mutex<&fs::File>,
+) -> io::Result<()> {
+    let mut reader = io::BufReader::new(reader);
+    let mut line = Vec::new();
+    while io::BufRead::read_until(&mut reader, b'\n', &mut line)? > 0 {
+        terminal.write_all(&line)?;
+        terminal.flush()?;
+        let mut log = log
+            .lock()
+            .map_err(|_| io::Error::other("The log file is unavailable"))?;
+        write!(log, "[{}] ", now_timestamp())?;
+        log.write_all(&line)?;
+        if !line.ends_with(b"\n") {
+            log.write_all(b"\n")?;
+        }
+        line.clear();
+    }
+    Ok(())
+}
+
+/// Waits for `child` like `wait_with_timeout` while copying its piped stdout and stderr to the
+/// terminal and to `log`, each line prefixed with a timestamp (see `tee_lines`).
+///
+/// The two streams are read on separate threads so neither can fill up and block the child. A
+/// failure to write the log is reported as a warning; the child keeps running.
+fn wait_with_log(
+    child: &mut Child,
+    timeout: Option<Duration>,
+    log: &fs::File,
+) -> io::Result<ExitStatus> {
+    let log = std::sync::Mutex::new(log);
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    std::thread::scope(|scope| {
+        let log = &log;
+        let readers = [
+            scope.spawn(move || stdout.map_or(Ok(()), |out| tee_lines(out, io::stdout(), log))),
+            scope.spawn(move || stderr.map_or(Ok(()), |err| tee_lines(err, io::stderr(), log))),
+        ];
+        let status = wait_with_timeout(child, timeout);
+        for reader in readers {
+            if let Ok(Err(e)) = reader.join() {
+                eprintln!(
+                    "{}",
+                    warning(&format!("Warning: could not log output: {}", e))
+                );
+            }
+        }
+        status
+    })
+}
+
+/// Runs a shortcut `times` times in a row with `run_shortcut`, e.g. to find flaky tests.
+///
+/// Each run is announced with its number (`[1/5] Running 'name'...`). A run that exits unsuccessfully
+/// or cannot be started counts as failed; with `fail_fast`, no further runs are started after it.
+///
+/// # Returns
+/// * `Ok((successes, runs))` with the number of successful runs and the number of runs started.
+/// * `Err(io::Error)` if no shortcut is named `name` or the shortcuts cannot be loaded.
+fn repeat_shortcut(
+    name: &str,
+    extra_args: &[String],
+    options: &RunOptions,
+    times: u32,
+    fail_fast: bool,
+) -> io::Result<(u32, u32)> {
+    let shortcuts = load_visible_shortcuts()?;
+    if !shortcuts.iter().any(|s| names_match(&s.project_name, name)) {
+        return Err(shortcut_not_found(name));
+    }
+    let mut successes = 0;
+    let mut runs = 0;
+    for i in 1..=times {
+        log!("[{}/{}] Running '{}'...", i, times, highlight(name));
+        runs += 1;
+        match run_shortcut(name, extra_args.to_vec(), options) {
+            Ok(status) if status.success() => successes += 1,
+            Ok(status) => eprintln!("{}", warning(&format!("Run {} exited with {}", i, status))),
+            Err(e) => eprintln!("{}", warning(&format!("Run {} failed: {}", i, e))),
+        }
+        if fail_fast && successes < runs {
+            break;
+        }
+    }
+    Ok((successes, runs))
+}
+
+/// Runs a shortcut with `run_shortcut`, then runs a follow-up shortcut depending on the outcome.
+///
+/// `on_success` is run if the command exited with status 0, and `on_failure` if it exited with
+/// another status or could not be run at all. Follow-ups run without extra arguments or options,
+/// and a follow-up that fails is reported as a warning without changing the result. Nothing
+/// follows a dry run.
+///
+/// # Returns
+/// The result of running the shortcut named `name`, as returned by `run_shortcut`.
+fn run_with_follow_up(
+    name: &str,
+    extra_args: Vec<String>,
+    options: &RunOptions,
+    on_success: Option<&str>,
+    on_failure: Option<&str>,
+) -> io::Result<ExitStatus> {
+    let result = run_shortcut(name, extra_args, options);
+    let succeeded = matches!(&result, Ok(status) if status.success());
+    let follow_up = if succeeded { on_success } else { on_failure };
+    if let Some(next) = follow_up.filter(|_| !options.dry_run) {
+        log!("Running follow-up shortcut '{}'", highlight(next));
+        match run_shortcut(next, vec![], &RunOptions::default()) {
+            Ok(status) if !status.success() => eprintln!(
+                "{}",
+                warning(&format!(
+                    "Warning: follow-up '{}' exited with {}",
+                    next, status
+                ))
+            ),
+            Ok(_) => {}
+            Err(e) => eprintln!(
+                "{}",
+                warning(&format!(
+                    "Warning: could not run follow-up '{}': {}",
+                    next, e
+                ))
+            ),
+        }
+    }
+    result
+}
+
+/// Runs a `pre_run` or `post_run` command of `shortcut` and waits for it, with the working
+/// directory and environment of the shortcut, limited like the main command's by `options` (see
+/// `restrict_env`). `label` names the hook in the error message.
+///
+/// # Errors
+/// Returns an error if the command cannot be started or exits unsuccessfully.
+fn run_hook(
+    shortcut: &Shortcut,
+    hook: &[String],
+    label: &str,
+    options: &RunOptions,
+) -> io::Result<()> {
+    let Some((command, args)) = hook.split_first() else {
+        return Ok(());
+    };
+    let mut process = build_process(shortcut, command, args);
+    restrict_env(&mut process, shortcut, options);
+    let status = process.status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "The {} command `{}` failed with {}",
+            label,
+            command_line(hook),
+            status
+        )))
+    }
+}
+
+/// Replaces every `{NAME}` placeholder in `command` with the value `lookup` returns for `NAME`.
+///
+/// A placeholder is a name made of uppercase ASCII letters and `_` in braces, and may be a whole
+/// token (`{BRANCH}`) or part of one (`--version={VERSION}`). Other text in braces is kept as is.
+/// `lookup` is called once per distinct name, in the order the names first appear.
+///
+/// # Errors
+/// Returns the first error returned by `lookup`.
+fn substitute_placeholders(
+    command: &[String],
+    mut lookup: impl FnMut(&str) -> io::Result<String>,
+) -> io::Result<Vec<String>> {
+    let mut values: HashMap<String, String> = HashMap::new();
+    let mut result = Vec::with_capacity(command.len());
+    for token in command {
+        let mut resolved = String::with_capacity(token.len());
+        let mut rest = token.as_str();
+        while let Some(start) = rest.find('{') {
+            resolved.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let end = after
+                .find(|c: char| !(c.is_ascii_uppercase() || c == '_'))
+                .unwrap_or(after.len());
+            if end > 0 && after[end..].starts_with('}') {
+                let name = &after[..end];
+                if !values.contains_key(name) {
+                    values.insert(name.to_string(), lookup(name)?);
+                }
+                resolved.push_str(&values[name]);
+                rest = &after[end + 1..];
+            } else {
+                resolved.push('{');
+                rest = after;
+            }
+        }
+        resolved.push_str(rest);
+        result.push(resolved);
+    }
+    Ok(result)
+}
+
+/// Fills in the `{NAME}` placeholders of a template shortcut's command (see
+/// `substitute_placeholders`). Values given in `values` (from `run --set`) are used first; any
+/// other placeholder is asked for on stdin.
+///
+/// # Errors
+/// Returns `io::ErrorKind::InvalidInput` if a placeholder has no value in `values` and stdin is not
+/// a terminal, so there is no one to ask, and an error if an answer cannot be read.
+fn resolve_template_args(
+    command: &[String],
+    values: &HashMap<String, String>,
+) -> io::Result<Vec<String>> {
+    substitute_placeholders(command, |name| {
+        if let Some(value) = values.get(name) {
+            return Ok(value.clone());
+        }
+        if !io::stdin().is_terminal() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "The command needs a value for {{{0}}}, but stdin is not a terminal to ask for it. Pass --set {0}=VALUE to provide it.",
+                    name
+                ),
+            ));
+        }
+        prompt_line(&mut io::stdin().lock(), &format!("{}: ", name))
+    })
+}
+
+/// Calls `record_run`, printing a warning instead of failing if the run cannot be recorded.
+fn note_run(name: &str) {
+    if let Err(e) = record_run(name) {
+        eprintln!(
+            "{}",
+            warning(&format!(
+                "Warning: could not record the run of '{}': {}",
+                name, e
+            ))
+        );
+    }
+}
+
+/// Records that the shortcut named `name` was just run.
+///
+/// The shortcuts are loaded again because the command may have run for a long time, during which
+/// another projexts process may have changed the configuration. The shortcut's `run_count` is
+/// incremented and `last_run_at` set to the current UTC time.
+///
+/// Runs of shortcuts from a project-local `.projexts.json` are not recorded, so that running them
+/// does not change a file that is usually kept in version control. Recording a run takes no backup
+/// (see `write_shortcuts`).
+///
+/// # Errors
+/// Returns an error if the shortcuts cannot be loaded or saved.
+fn record_run(name: &str) -> io::Result<()> {
+    if load_local_shortcuts()?
+        .iter()
+        .any(|s| names_match(&s.project_name, name))
+    {
+        return Ok(());
+    }
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    if let Some(shortcut) = shortcuts
+        .iter_mut()
+        .find(|s| names_match(&s.project_name, name))
+    {
+        shortcut.run_count += 1;
+        shortcut.last_run_at = Some(now_timestamp());
+        write_shortcuts(&shortcuts)?;
+    }
+    Ok(())
+}
+
+/// Runs every shortcut, or every shortcut with one of the given tags, one after another.
+///
+/// Each shortcut is run with `run_shortcut` in storage order. A shortcut fails if its command could
+/// not be run or exited with a non-zero code. With `fail_fast`, the first failure stops the run;
+/// otherwise the remaining shortcuts still run and all failures are reported at the end.
+///
+/// # Arguments
+/// * `tags` - Only run shortcuts that have at least one of these tags. If empty, run all shortcuts.
+/// * `fail_fast` - Whether to stop at the first failing shortcut.
+///
+/// # Returns
+/// * `Ok(())` if every selected shortcut succeeded.
+/// * `Err(io::Error)` naming the failed shortcuts if any of them failed.
+/// * `Err(io::Error)` if an error occurs while loading the shortcuts.
+fn run_all_shortcuts(tags: &[String], fail_fast: bool) -> io::Result<()> {
+    let shortcuts = load_visible_shortcuts()?;
+    let selected: Vec<&Shortcut> = shortcuts
+        .iter()
+        .filter(|s| tags.is_empty() || s.tags.iter().any(|tag| tags.contains(tag)))
+        .collect();
+
+    let mut failed = Vec::new();
+    for shortcut in &selected {
+        let name = &shortcut.project_name;
+        log!("{}", highlight(&format!("==> {}", name)));
+        let problem = match run_shortcut(name, Vec::new(), &RunOptions::default()) {
+            Ok(status) if status.success() => None,
+            Ok(status) => Some(format!("exited with {}", status)),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(problem) = problem {
+            eprintln!("{}", failure(&format!("'{}' failed: {}", name, problem)));
+            failed.push(name.clone());
+            if fail_fast {
+                break;
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        log!(
+            "{}",
+            success(&format!("All {} shortcuts succeeded.", selected.len()))
+        );
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "{} of {} shortcuts failed: {}",
+            failed.len(),
+            selected.len(),
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Runs several shortcuts at the same time and reports the exit code of each.
+///
+/// All names are looked up before anything is started, so a typo does not leave some commands
+/// running. At most `max_jobs` commands run at once: that many worker threads take the next
+/// shortcut from a shared queue, run it, and send its outcome back over a channel. Output of the
+/// commands is not buffered, so lines from different commands may be interleaved.
+///
+/// As with `run_shortcut`, each shortcut's `pre_run` command runs first, and the shortcut fails
+/// without running if it fails, and its `post_run` command runs afterwards. The `{NAME}`
+/// placeholders of template shortcuts are filled in before anything starts (see
+/// `resolve_template_args`), so the questions are not mixed up with the commands' output.
+///
+/// # Arguments
+/// * `names` - The names of the shortcuts to run.
+/// * `max_jobs` - The maximum number of commands to run at the same time (at least 1).
+///
+/// # Returns
+/// * `Ok(())` if every command exited with code 0.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if one of the names matches no shortcut.
+/// * `Err(io::Error)` from `resolve_template_args` if a placeholder cannot be filled in.
+/// * `Err(io::Error)` naming the failed shortcuts if any command failed or could not be started.
+fn run_parallel_shortcuts(names: &[String], max_jobs: usize) -> io::Result<()> {
+    let shortcuts = load_visible_shortcuts()?;
+    let mut selected = Vec::new();
+    for name in names {
+        let shortcut = shortcuts
+            .iter()
+            .find(|s| names_match(&s.project_name, name))
+            .ok_or_else(|| shortcut_not_found(name))?;
+        if shortcut.run_command.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Command for '{}' is empty", name),
+            ));
+        }
+        let command = resolve_template_args(&shortcut.run_command, &HashMap::new())?;
+        selected.push((shortcut, command));
+    }
+
+    let queue = std::sync::Mutex::new(selected.iter().enumerate());
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..max_jobs.max(1).min(selected.len()) {
+            let sender = sender.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                let Some((index, (shortcut, full_command))) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let (command, args) = full_command
+                    .split_first()
+                    .expect("empty commands were rejected above");
+                let status = match &shortcut.pre_run {
+                    Some(pre_run) => run_hook(shortcut, pre_run, "pre-run", &RunOptions::default()),
+                    None => Ok(()),
+                }
+                .and_then(|()| {
+                    let status = build_process(shortcut, command, args)
+                        .spawn()
+                        .and_then(|mut child| child.wait());
+                    if let Some(post_run) = &shortcut.post_run {
+                        let options = RunOptions::default();
+                        if let Err(e) = run_hook(shortcut, post_run, "post-run", &options) {
+                            let message = format!("Warning: {}: {}", shortcut.project_name, e);
+                            eprintln!("{}", warning(&message));
+                        }
+                    }
+                    status
+                });
+                let _ = sender.send((index, status));
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<(usize, io::Result<ExitStatus>)> = receiver.into_iter().collect();
+    results.sort_by_key(|(index, _)| *index);
+    let mut failed = Vec::new();
+    for (index, result) in results {
+        let name = &selected[index].0.project_name;
+        // Runs are recorded one at a time from here, in the order the shortcuts were given
+        if result.is_ok() {
+            note_run(name);
+        }
+        match result {
+            Ok(status) if status.success() => {
+                log!("{}", success(&format!("{}: exited with code 0", name)))
+            }
+            Ok(status) => {
+                let code = status
+                    .code()
+                    .map_or_else(|| "no code".to_string(), |code| code.to_string());
+                eprintln!(
+                    "{}",
+                    failure(&format!("{}: exited with code {}", name, code))
+                );
+                failed.push(name.clone());
+            }
+            Err(e) => {
+                eprintln!("{}", failure(&format!("{}: failed to start: {}", name, e)));
+                failed.push(name.clone());
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "{} of {} shortcuts failed: {}",
+            failed.len(),
+            selected.len(),
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Updates the command, tags, and description of an existing shortcut.
+///
+/// This function searches for a shortcut by its name and updates its associated command if found.
+/// If a new command is provided, it replaces the existing command for that shortcut. Tags listed in
+/// `options` are added to or removed from the shortcut, and a new description or working directory
+/// replaces the old one. The working directory is converted to an absolute path and must exist.
+/// Environment variables are set or unset individually, leaving the others in place. Single
+/// arguments can be added, inserted, or removed without retyping the rest of the command (see
+/// `edit_arguments`); these changes apply after any new command. A new name in `options` is
+/// checked like in `rename_shortcut` and saved together with the other changes.
+/// If the shortcut is found and updated successfully, the changes are saved to storage.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut to update.
+/// * `new_command` - An optional vector of new command arguments. If `Some(command)` is provided,
+///   the command associated with the shortcut will be replaced with this new command. If `None` is
+///   provided, the command will not be changed.
+/// * `options` - The tags and environment variables to change and the new description and working
+///   directory, if any.
+///
+/// # Returns
+/// * `Ok(())` if the shortcut is found and updated successfully, and the changes are saved.
+/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts, or if the shortcut
+///   with the given name is not found.
+///
+/// # Errors
+/// The function will return an error if:
+/// - No shortcut with the given name is found.
+/// - The new working directory does not exist or is not a directory.
+/// - The argument to remove is not in the command, or the insertion position is out of range.
+/// - The new name is not a valid shortcut name or is already taken by another shortcut.
+/// - The shortcut is locked and `options.allow_locked` is not set
+///   (`io::ErrorKind::PermissionDenied`).
+/// - An error occurs while saving the updated list of shortcuts to storage.
+fn update_shortcut(
+    name: &str,
+    new_command: Option<Vec<String>>,
+    options: &UpdateOptions,
+) -> io::Result<()> {
+    debug_log!(
+        "update_shortcut(name: {:?}, new_command: {:?}, options: {:?})",
+        name,
+        new_command,
+        options
+    );
+    if let Some(new_name) = &options.new_name {
+        validate_shortcut_name(new_name)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    }
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    if let Some(new_name) = &options.new_name {
+        // Renaming to the same name with different capitalization is allowed
+        let taken = shortcuts
+            .iter()
+            .any(|s| !names_match(&s.project_name, name) && names_match(&s.project_name, new_name));
+        if taken {
+            return Err(shortcut_already_exists(new_name));
+        }
+    }
+    if let Some(shortcut) = shortcuts
+        .iter_mut()
+        .find(|s| names_match(&s.project_name, name))
+    {
+        if shortcut.locked && !options.allow_locked {
+            return Err(shortcut_locked(&shortcut.project_name));
+        }
+        if let Some(new_name) = &options.new_name {
+            shortcut.project_name = new_name.clone();
+        }
+        if let Some(new_command) = new_command {
+            shortcut.run_command = new_command;
+        }
+        edit_arguments(&mut shortcut.run_command, options)?;
+        add_tags(&mut shortcut.tags, &options.add_tags);
+        shortcut
+            .tags
+            .retain(|tag| !options.remove_tags.contains(tag));
+        if let Some(description) = &options.description {
+            shortcut.description = Some(description.clone()).filter(|d| !d.is_empty());
+        }
+        if let Some(dir) = &options.working_dir {
+            shortcut.working_dir = Some(resolve_working_dir(dir)?);
+        }
+        shortcut.env.extend(options.set_env.iter().cloned());
+        for key in &options.unset_env {
+            shortcut.env.remove(key);
+        }
+        if let Some(pre_run) = &options.pre_run {
+            shortcut.pre_run = Some(pre_run.clone()).filter(|hook| !hook.is_empty());
+        }
+        if let Some(post_run) = &options.post_run {
+            shortcut.post_run = Some(post_run.clone()).filter(|hook| !hook.is_empty());
+        }
+        shortcut.updated_at = Some(now_timestamp());
+        save_shortcuts(&shortcuts)?;
+        log!(
+            "{}",
+            success(&format!("Shortcut '{}' updated successfully.", name))
+        );
+        Ok(())
+    } else {
+        Err(shortcut_not_found(name))
+    }
+}
+
+/// Returns the error reported when no shortcut matches `name`.
+///
+/// If a stored shortcut's name is close to `name` (see `suggestion::closest_match`), the message
+/// ends with "Did you mean '<name>'?".
+fn shortcut_not_found(name: &str) -> io::Error {
+    let mut message = format!("No shortcut found with name '{}'", name);
+    // Only a hint, so a config that can't be read just means no suggestion
+    let shortcuts = load_visible_shortcuts().unwrap_or_default();
+    let names = shortcuts.iter().map(|s| s.project_name.as_str());
+    if let Some(closest) = suggestion::closest_match(name, names) {
+        message.push_str(&format!(". Did you mean '{}'?", closest));
+    }
+    io::Error::new(io::ErrorKind::NotFound, message)
+}
+
+/// Returns the error reported when the locked shortcut `name` would be changed or removed.
+fn shortcut_locked(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!(
+            "Shortcut '{}' is locked. Unlock it with 'projexts unlock {}', or pass --force to remove, update, or rename it anyway.",
+            name, name
+        ),
+    )
+}
+
+/// Locks a shortcut.
+///
+/// A locked shortcut cannot be removed, updated, or renamed unless `--force` is passed to `remove`
+/// or `update`, which protects important shortcuts from accidental changes.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut.
+///
+/// # Returns
+/// * `Ok(())` if the change was saved.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name`.
+/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts.
+fn lock_shortcut(name: &str) -> io::Result<()> {
+    set_locked(name, true)
+}
+
+/// Unlocks a shortcut locked with `lock_shortcut`, so it can be changed and removed again.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::NotFound` if no shortcut is named `name`, or if the
+/// shortcuts cannot be loaded or saved.
+fn unlock_shortcut(name: &str) -> io::Result<()> {
+    set_locked(name, false)
+}
+
+/// Sets whether the shortcut named `name` is locked, for `lock_shortcut` and `unlock_shortcut`.
+fn set_locked(name: &str, locked: bool) -> io::Result<()> {
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    let shortcut = shortcuts
+        .iter_mut()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+    shortcut.locked = locked;
+    save_shortcuts(&shortcuts)
+}
+
+/// Returns the error reported when a shortcut named `name` already exists.
+fn shortcut_already_exists(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("A shortcut named '{}' already exists", name),
+    )
+}
+
+/// Renames an existing shortcut, keeping all of its other fields.
+///
+/// # Arguments
+/// * `old` - The current name of the shortcut.
+/// * `new` - The name to give the shortcut.
+/// * `allow_locked` - Rename the shortcut even if it is locked (`rename --force`).
+///
+/// # Returns
+/// * `Ok(())` if the shortcut was renamed and the change saved.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `old`.
+/// * `Err(io::Error)` with `io::ErrorKind::AlreadyExists` if another shortcut is already named `new`.
+/// * `Err(io::Error)` with `io::ErrorKind::InvalidInput` if `new` is not a valid shortcut name.
+/// * `Err(io::Error)` with `io::ErrorKind::PermissionDenied` if the shortcut is locked and
+///   `allow_locked` is not set.
+/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts.
+fn rename_shortcut(old: &str, new: &str, allow_locked: bool) -> io::Result<()> {
+    debug_log!(
+        "rename_shortcut(old: {:?}, new: {:?}, allow_locked: {})",
+        old,
+        new,
+        allow_locked
+    );
+    validate_shortcut_name(new).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    let index = shortcuts
+        .iter()
+        .position(|s| names_match(&s.project_name, old))
+        .ok_or_else(|| shortcut_not_found(old))?;
+    if shortcuts[index].locked && !allow_locked {
+        return Err(shortcut_locked(&shortcuts[index].project_name));
+    }
+
+    // Renaming to the same name with different capitalization is allowed
+    let taken = shortcuts
+        .iter()
+        .enumerate()
+        .any(|(i, s)| i != index && names_match(&s.project_name, new));
+    if taken {
+        return Err(shortcut_already_exists(new));
+    }
+
+    shortcuts[index].project_name = new.to_string();
+    shortcuts[index].updated_at = Some(now_timestamp());
+    save_shortcuts(&shortcuts)
+}
+
+/// Creates a copy of an existing shortcut under a new name.
+///
+/// All fields except the name, the usage statistics, and the timestamps are copied verbatim, and the
+/// copy is added after the existing shortcuts. The copy starts out as newly created and never run.
+///
+/// # Arguments
+/// * `source` - The name of the shortcut to copy.
+/// * `dest` - The name to give the copy.
+///
+/// # Returns
+/// * `Ok(())` if the copy was added and saved.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `source`.
+/// * `Err(io::Error)` with `io::ErrorKind::AlreadyExists` if a shortcut is already named `dest`.
+/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts.
+fn duplicate_shortcut(source: &str, dest: &str) -> io::Result<()> {
+    debug_log!("duplicate_shortcut(source: {:?}, dest: {:?})", source, dest);
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    let mut copy = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, source))
+        .cloned()
+        .ok_or_else(|| shortcut_not_found(source))?;
+    if shortcuts.iter().any(|s| names_match(&s.project_name, dest)) {
+        return Err(shortcut_already_exists(dest));
+    }
+
+    copy.project_name = dest.to_string();
+    copy.locked = false;
+    copy.run_count = 0;
+    copy.last_run_at = None;
+    copy.created_at = Some(now_timestamp());
+    copy.updated_at = None;
+    shortcuts.push(copy);
+    save_shortcuts(&shortcuts)
+}
+
+/// Opens a file from a shortcut's command list.
+///
+/// This function searches for a shortcut by its name and attempts to open each file path in the shortcut's
+/// command list. The file paths are opened with the system's default application, or in `app` if one
+/// is given (see `open_command`). The function will open each file path as long as the path exists and
+/// is a valid file.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose command list will be used to find and open the file paths.
+/// * `app` - The application to open the files in, such as an editor, instead of the default one.
+///
+/// # Returns
+/// * `Ok(())` if the file(s) were opened successfully.
+/// * `Err(io::Error)` if an error occurs while loading the shortcuts, or if the shortcut with the given
+///   name is not found, or if any file in the shortcut's command list cannot be opened.
+///
+/// # Errors
+/// The function will return an error if:
+/// - No shortcut with the given name is found (`io::ErrorKind::NotFound`).
+/// - The `run_command` for the shortcut is empty (`io::ErrorKind::InvalidData`).
+/// - Any of the paths in the shortcut are invalid, do not exist, or are not files.
+/// - The operating system is unsupported for file opening commands.
+/// - `app` is blank on Linux.
+fn open_file_from_shortcut(name: &str, app: Option<&str>) -> io::Result<()> {
+    let shortcuts = load_visible_shortcuts()?;
+    let shortcut = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+    if shortcut.run_command.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Run command is empty for shortcut '{}'",
+                shortcut.project_name
+            ),
+        ));
+    }
+    let open_command = open_command(app)?;
+
+    for file_path in &shortcut.run_command {
+        let path = Path::new(file_path);
+
+        if path.exists() && path.is_file() {
+            Command::new(&open_command[0])
+                .args(&open_command[1..])
+                .arg(path)
+                .spawn()?
+                .wait()?; // Wait for the command to complete
+            log!("Opening file: {:?}", file_path);
+        } else {
+            eprintln!(
+                "{}",
+                failure(&format!(
+                    "Error: '{}' does not exist or is not a file.",
+                    file_path
+                ))
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the project directory of the shortcut named `name`, as found by `resolve_project_dir`.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::NotFound` if no shortcut is named `name`, or any error from
+/// loading the shortcuts or `resolve_project_dir`.
+fn find_project_dir(name: &str) -> io::Result<PathBuf> {
+    let shortcuts = load_visible_shortcuts()?;
+    let shortcut = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+    resolve_project_dir(shortcut)
+}
+
+/// Checks that a shortcut named `name` can be added for a repository at `path` before
+/// `git_init_and_add` or `git_clone_and_add` create it.
+///
+/// # Returns
+/// `path` as a string, for the shortcut's command.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::InvalidInput` if `name` is not a valid shortcut name or
+/// `path` is not valid UTF-8, and with `io::ErrorKind::AlreadyExists` if a shortcut is already named
+/// `name`.
+fn check_new_project<'a>(name: &str, path: &'a Path) -> io::Result<&'a str> {
+    validate_shortcut_name(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    if load_shortcuts()?
+        .iter()
+        .any(|s| names_match(&s.project_name, name))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("A shortcut named '{}' already exists.", name),
+        ));
+    }
+    path.to_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{:?} is not valid UTF-8", path),
+        )
+    })
+}
+
+/// Starts a new project: creates a Git repository at `path` and adds a shortcut named `name` whose
+/// command is the repository's directory.
+///
+/// The name is checked before anything is created, so an invalid or taken name leaves no
+/// repository behind. `git init` creates `path` if it does not exist yet.
+///
+/// # Arguments
+/// * `name` - The name of the new shortcut.
+/// * `path` - Where to create the repository.
+/// * `branch` - The name of the initial branch (`git init -b`). Git's default is used if `None`.
+///
+/// # Returns
+/// * `Ok(())` if the repository was created and the shortcut added.
+/// * `Err(io::Error)` with `io::ErrorKind::AlreadyExists` if `path` already is a Git repository or
+///   a shortcut is already named `name`.
+/// * `Err(io::Error)` with `io::ErrorKind::InvalidInput` if `name` is not a valid shortcut name or
+///   `path` is not valid UTF-8.
+/// * `Err(io::Error)` if `git init` could not be run or failed, or the shortcut could not be saved.
+fn git_init_and_add(name: &str, path: &Path, branch: Option<&str>) -> io::Result<()> {
+    let dir = check_new_project(name, path)?;
+    if path.join(".git").exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{:?} is already a Git repository", path),
+        ));
+    }
+
+    let mut init = Command::new("git");
+    init.arg("init");
+    if let Some(branch) = branch {
+        init.arg("-b").arg(branch);
+    }
+    let output = init.arg(dir).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git init exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    add_shortcut(name, vec![dir.to_string()], &AddOptions::default())?;
+    log!(
+        "{}",
+        success(&format!(
+            "Created a Git repository in {:?} and added shortcut '{}'.",
+            path, name
+        ))
+    );
+    Ok(())
+}
+
+/// Returns the directory name `git clone` would pick for `url`: its last path component without a
+/// trailing `.git`, e.g. `projexts` for `https://github.com/user/projexts.git` or
+/// `git@github.com:user/projexts.git`.
+///
+/// Returns `None` if the URL has no usable last component.
+fn repo_name_from_url(url: &str) -> Option<String> {
+    let url = url.trim_end_matches('/');
+    let last = url.rsplit(['/', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Clones a Git repository and adds a shortcut named `name` whose command is the cloned directory.
+///
+/// The name is checked before cloning, so an invalid or taken name doesn't leave a clone behind.
+/// Git's progress output is shown while cloning.
+///
+/// # Arguments
+/// * `url` - The repository to clone.
+/// * `name` - The name of the new shortcut.
+/// * `dest` - The directory to clone into, which must not exist yet. See `repo_name_from_url` for
+///   the default used by `git-clone`.
+///
+/// # Returns
+/// * `Ok(())` if the repository was cloned and the shortcut added.
+/// * `Err(io::Error)` with `io::ErrorKind::AlreadyExists` if `dest` exists or a shortcut is already
+///   named `name`.
+/// * `Err(io::Error)` with `io::ErrorKind::InvalidInput` if `name` is not a valid shortcut name or
+///   `dest` is not valid UTF-8.
+/// * `Err(io::Error)` if `git clone` could not be run or failed, or the shortcut could not be saved.
+fn git_clone_and_add(url: &str, name: &str, dest: &Path) -> io::Result<()> {
+    let dir = check_new_project(name, dest)?;
+    if dest.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{:?} already exists", dest),
+        ));
+    }
+
+    let status = Command::new("git")
+        .arg("clone")
+        .arg(url)
+        .arg(dir)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "git clone exited with {}",
+            status
+        )));
+    }
+    add_shortcut(name, vec![dir.to_string()], &AddOptions::default())?;
+    log!(
+        "{}",
+        success(&format!(
+            "Cloned {} into {:?} and added shortcut '{}'.",
+            url, dest, name
+        ))
+    );
+    Ok(())
+}
+
+/// Commits changes in the Git repository of a shortcut's project directory without pushing them.
+///
+/// By default all changes are staged with `git add .` before committing, which includes new files.
+/// With `all`, `git commit -a` is used instead, which only commits changes to files Git already
+/// tracks. With `amend`, the changes and message replace the last commit instead.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+/// * `commit_message` - The commit message to use for the `git commit` command.
+/// * `all` - Whether to pass `-a` to `git commit` instead of running `git add .` first.
+/// * `amend` - Whether to pass `--amend` to `git commit`.
+///
+/// # Returns
+/// * `Ok(())` if the changes were committed.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if Git could not be run or `git add` or `git commit` failed, e.g. because
+///   there was nothing to commit or no Git identity is configured.
+fn git_commit(name: &str, commit_message: &str, all: bool, amend: bool) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+
+    let mut commit = Command::new("git");
+    commit.arg("commit").current_dir(&dir);
+    if amend {
+        commit.arg("--amend");
+    }
+    if all {
+        commit.arg("-a");
+    } else {
+        // Add changes
+        let status = Command::new("git")
+            .arg("add")
+            .arg(".")
+            .current_dir(&dir)
+            .status()?;
+        if !status.success() {
+            return Err(io::Error::other(format!("git add exited with {}", status)));
+        }
+    }
+    let status = commit.arg("-m").arg(commit_message).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "git commit exited with {}",
+            status
+        )));
+    }
+
+    log!(
+        "{}",
+        success(&format!("Changes committed in directory {:?}", dir))
+    );
+    Ok(())
+}
+
+/// Options that control where `git_push` pushes to.
+#[derive(Default, Debug, Clone)]
+struct PushOptions {
+    /// The remote to push to. Defaults to `origin`.
+    remote: Option<String>,
+    /// The branch to push. Defaults to `HEAD`, the current branch.
+    branch: Option<String>,
+    /// Overwrite the remote branch with `--force-with-lease`.
+    force: bool,
+    /// Refuse commit messages that don't follow Conventional Commits (see
+    /// `validate_conventional_commit`).
+    conventional: bool,
+    /// Amend the last commit instead of creating a new one. The push is then always forced.
+    amend: bool,
+}
+
+/// The commit types `validate_conventional_commit` accepts.
+const CONVENTIONAL_COMMIT_TYPES: [&str; 8] = [
+    "feat", "fix", "chore", "docs", "style", "refactor", "test", "perf",
+];
+
+/// Checks whether a commit message follows the Conventional Commits format.
+///
+/// The first line must be a type from `CONVENTIONAL_COMMIT_TYPES`, optionally followed by a scope in
+/// parentheses, then `: ` and a description of 1 to 72 characters, e.g. `feat(cli): add doctor`.
+/// This is the regex `^(feat|fix|chore|docs|style|refactor|test|perf)(\(.+\))?: .{1,72}$` applied
+/// to the first line, so a body may follow.
+fn validate_conventional_commit(msg: &str) -> bool {
+    let subject = msg.lines().next().unwrap_or("");
+    let Some(rest) = CONVENTIONAL_COMMIT_TYPES
+        .iter()
+        .find_map(|kind| subject.strip_prefix(kind))
+    else {
+        return false;
+    };
+    let valid_description = |description: &str| (1..=72).contains(&description.chars().count());
+
+    if rest.strip_prefix(": ").is_some_and(valid_description) {
+        return true;
+    }
+    // The scope may itself contain "): ", so try every place it could end
+    rest.strip_prefix('(').is_some_and(|scoped| {
+        scoped
+            .match_indices("): ")
+            .any(|(end, _)| end > 0 && valid_description(&scoped[end + 3..]))
+    })
+}
+
+/// Commits and pushes changes to a Git repository using a shortcut's project directory.
+///
+/// This function stages and commits all changes with `git_commit` and then runs
+/// `git push <remote> <branch>` in the shortcut's project directory. A forced push uses
+/// `--force-with-lease`, so it fails instead of discarding commits someone else pushed. When
+/// amending, the last commit is rewritten, so the push is forced even without `options.force`.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+/// * `commit_message` - The commit message to use for the `git commit` command.
+/// * `options` - The remote and branch to push to, whether to force the push or amend the last
+///   commit, and whether to require a Conventional Commits message.
+///
+/// # Returns
+/// * `Ok(())` if the Git operations (add, commit, push) were successful.
+/// * `Err(io::Error)` if any error occurs during the Git operations, loading shortcuts, or if the shortcut
+///   cannot be found.
+///
+/// # Errors
+/// The function will return an error if:
+/// - `options.conventional` is set and `commit_message` is not a Conventional Commits message
+///   (`io::ErrorKind::InvalidInput`). Nothing is committed in that case.
+/// - No shortcut with the given name is found.
+/// - The directory from the shortcut's `run_command` cannot be determined or is invalid.
+/// - Any of the Git commands (`git add`, `git commit`, `git push`) cannot be run or fails, e.g.
+///   because the push was rejected.
+fn git_push(name: &str, commit_message: &str, options: &PushOptions) -> io::Result<()> {
+    if options.conventional && !validate_conventional_commit(commit_message) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "'{}' is not a Conventional Commits message. Use '<type>(<scope>): <description>', where <type> is one of {}, the scope is optional, and the description is 1 to 72 characters.",
+                commit_message.lines().next().unwrap_or(""),
+                CONVENTIONAL_COMMIT_TYPES.join(", ")
+            ),
+        ));
+    }
+    // Stop if the commit failed, so a failed amend is never force-pushed
+    git_commit(name, commit_message, false, options.amend)?;
+    let dir = find_project_dir(name)?;
+    push_dir(&dir, options)?;
+
+    log!(
+        "{}",
+        success(&format!("Changes pushed from directory {:?}", dir))
+    );
+    Ok(())
+}
+
+/// Runs `git push <remote> <branch>` in `dir`, as described for `git_push`.
+///
+/// # Errors
+/// Returns an error if Git could not be run or the push failed, e.g. because it was rejected.
+fn push_dir(dir: &Path, options: &PushOptions) -> io::Result<()> {
+    let mut push = Command::new("git");
+    push.arg("push").current_dir(dir);
+    if options.force || options.amend {
+        push.arg("--force-with-lease");
+    }
+    let status = push
+        .arg(options.remote.as_deref().unwrap_or("origin"))
+        .arg(options.branch.as_deref().unwrap_or("HEAD"))
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("git push exited with {}", status)));
+    }
+    Ok(())
+}
+
+/// Pushes the commits of every shortcut's Git repository, without committing anything first.
+///
+/// Repositories are found the same way as for `git_pull_all`, and each is announced with the
+/// shortcut's name. A failed push is reported without stopping the others.
+///
+/// # Arguments
+/// * `options` - The remote and branch to push to, and whether to force the push.
+///
+/// # Returns
+/// * `Ok((pushed, failed))` with the number of repositories that were and weren't pushed.
+/// * `Err(io::Error)` if the shortcuts cannot be loaded.
+fn git_push_all(options: &PushOptions) -> io::Result<(usize, usize)> {
+    let (mut pushed, mut failed) = (0, 0);
+    for (name, dir) in git_repository_shortcuts()? {
+        log!("{}", highlight(&format!("{} ({:?})", name, dir)));
+        match push_dir(&dir, options) {
+            Ok(()) => pushed += 1,
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to push {}: {}", name, e)));
+                failed += 1;
+            }
+        }
+    }
+    Ok((pushed, failed))
+}
+
+/// Pulls changes into the Git repository of a shortcut's project directory.
+///
+/// The project directory is found with `resolve_project_dir`. The output of `git pull`
+/// is captured and printed. If the pull fails, for example because the working tree has uncommitted
+/// changes that would be overwritten, Git's explanation is printed as a warning.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+/// * `rebase` - Whether to pass `--rebase` to `git pull`.
+///
+/// # Returns
+/// * `Ok(())` if `git pull` succeeded.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if `git pull` could not be run or exited with an error.
+fn git_pull(name: &str, rebase: bool) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+
+    let mut command = Command::new("git");
+    command.arg("pull").current_dir(&dir);
+    if rebase {
+        command.arg("--rebase");
+    }
+    let output = command.output()?;
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        eprint!("{}", warning(&stderr));
+        return Err(io::Error::other(format!(
+            "git pull exited with {} in directory {:?}",
+            output.status, dir
+        )));
+    }
+    eprint!("{}", stderr);
+    log!(
+        "{}",
+        success(&format!("Changes pulled into directory {:?}", dir))
+    );
+    Ok(())
+}
+
+/// Returns whether `dir` is inside the work tree of a Git repository.
+fn is_git_repository(dir: &Path) -> bool {
+    Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(dir)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Returns the name and project directory of every shortcut whose directory is in a Git
+/// repository, for `git_pull_all` and `git_push_all`.
+///
+/// Shortcuts whose project directory (see `resolve_project_dir`) cannot be determined or is not
+/// in a Git repository are skipped, and a directory shared by several shortcuts is only listed
+/// for the first of them.
+///
+/// # Errors
+/// Returns an error if the shortcuts cannot be loaded.
+fn git_repository_shortcuts() -> io::Result<Vec<(String, PathBuf)>> {
+    let mut repositories: Vec<(String, PathBuf)> = Vec::new();
+    for shortcut in load_visible_shortcuts()? {
+        let Ok(dir) = resolve_project_dir(&shortcut) else {
+            continue;
+        };
+        if repositories.iter().any(|(_, seen)| *seen == dir) || !is_git_repository(&dir) {
+            continue;
+        }
+        repositories.push((shortcut.project_name, dir));
+    }
+    Ok(repositories)
+}
+
+/// Pulls changes into the Git repository of every shortcut, with `git_pull`.
+///
+/// The repositories are found with `git_repository_shortcuts`. Each pull is announced with the
+/// shortcut's name, and a failed pull is reported without stopping the others.
+///
+/// # Arguments
+/// * `rebase` - Whether to pass `--rebase` to `git pull`.
+///
+/// # Returns
+/// * `Ok((pulled, failed))` with the number of repositories that were and weren't pulled.
+/// * `Err(io::Error)` if the shortcuts cannot be loaded.
+fn git_pull_all(rebase: bool) -> io::Result<(usize, usize)> {
+    let (mut pulled, mut failed) = (0, 0);
+    for (name, dir) in git_repository_shortcuts()? {
+        log!("{}", highlight(&format!("{} ({:?})", name, dir)));
+        match git_pull(&name, rebase) {
+            Ok(()) => pulled += 1,
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to pull {}: {}", name, e)));
+                failed += 1;
+            }
+        }
+    }
+    Ok((pulled, failed))
+}
+
+/// Shows the current branch and the short status of a shortcut's Git repository.
+///
+/// The project directory is found with `resolve_project_dir`. The branch name, read with
+/// `git rev-parse --abbrev-ref HEAD`, is printed as a header, followed by the output of
+/// `git status --short`.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+///
+/// # Returns
+/// * `Ok(())` if the status was shown.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if the directory is not inside a Git repository or Git could not be run.
+fn git_status(name: &str) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+
+    let branch = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(&dir)
+        .output()?;
+    if !branch.status.success() {
+        return Err(io::Error::other(format!(
+            "{:?} is not inside a Git repository",
+            dir
+        )));
+    }
+    println!(
+        "On branch {}",
+        highlight(String::from_utf8_lossy(&branch.stdout).trim())
+    );
+
+    let status = Command::new("git")
+        .args(["status", "--short"])
+        .current_dir(&dir)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "git status exited with {}",
+            status
+        )));
+    }
+    Ok(())
+}
+
+/// Lists the branches of a shortcut's Git repository, highlighting the current one in green.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+/// * `all` - Whether to include remote-tracking branches (`git branch -a`).
+///
+/// # Returns
+/// * `Ok(())` if the branches were listed.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if the directory is not inside a Git repository or Git could not be run.
+fn git_branch(name: &str, all: bool) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+
+    let mut command = Command::new("git");
+    command.arg("branch").current_dir(&dir);
+    if all {
+        command.arg("-a");
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "{:?} is not inside a Git repository",
+            dir
+        )));
+    }
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        // Git marks the checked-out branch with a leading `* `
+        if line.starts_with("* ") {
+            println!("{}", success(line));
+        } else {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+/// Shows the most recent commits of a shortcut's Git repository, one per line.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+/// * `count` - The number of commits to show.
+///
+/// # Returns
+/// * `Ok(())` if the commits were shown.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if `git log` could not be run or exited with an error.
+fn git_log(name: &str, count: u32) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+
+    let status = Command::new("git")
+        .args(["log", "--oneline", "-n", &count.to_string()])
+        .current_dir(&dir)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("git log exited with {}", status)));
+    }
+    Ok(())
+}
+
+/// Shows the uncommitted changes in a shortcut's Git repository with `git diff`.
+///
+/// Git's output goes straight to the terminal, so its pager and colors work as usual. If there is
+/// nothing to show, "No changes to show." is printed instead.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+/// * `staged` - Whether to show the staged changes (`git diff --staged`) instead of the unstaged
+///   ones.
+///
+/// # Returns
+/// * `Ok(())` if the changes were shown.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if the directory is not inside a Git repository or Git could not be run.
+fn git_diff(name: &str, staged: bool) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+    let diff = || {
+        let mut command = Command::new("git");
+        command.arg("diff").current_dir(&dir);
+        if staged {
+            command.arg("--staged");
+        }
+        command
+    };
+
+    // `--quiet` exits with 1 if there are changes and 0 if there are none
+    let status = diff().arg("--quiet").stderr(Stdio::null()).status()?;
+    match status.code() {
+        Some(0) => {
+            log!("No changes to show.");
+            Ok(())
+        }
+        Some(1) => {
+            let status = diff().status()?;
+            if !status.success() {
+                return Err(io::Error::other(format!("git diff exited with {}", status)));
+            }
+            Ok(())
+        }
+        _ => Err(io::Error::other(format!(
+            "{:?} is not inside a Git repository",
+            dir
+        ))),
+    }
+}
+
+/// Stashes uncommitted changes in the Git repository of a shortcut's project directory.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+///
+/// # Returns
+/// * `Ok(())` if `git stash` succeeded.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if `git stash` could not be run or exited with an error.
+fn git_stash(name: &str) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+    let status = Command::new("git")
+        .arg("stash")
+        .current_dir(&dir)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "git stash exited with {}",
+            status
+        )));
+    }
+    log!(
+        "{}",
+        success(&format!("Changes stashed in directory {:?}", dir))
+    );
+    Ok(())
+}
+
+/// Restores the most recently stashed changes in the Git repository of a shortcut's project
+/// directory and removes them from the stash.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut whose associated Git project will be used.
+///
+/// # Returns
+/// * `Ok(())` if `git stash pop` succeeded.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name` or its directory
+///   cannot be determined.
+/// * `Err(io::Error)` if `git stash pop` could not be run or exited with an error, for example
+///   because there is no stash or the changes conflict.
+fn git_stash_pop(name: &str) -> io::Result<()> {
+    let dir = find_project_dir(name)?;
+    let status = Command::new("git")
+        .args(["stash", "pop"])
+        .current_dir(&dir)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "git stash pop exited with {}",
+            status
+        )));
+    }
+    log!(
+        "{}",
+        success(&format!("Stashed changes restored in directory {:?}", dir))
+    );
+    Ok(())
+}
+
+/// Returns the names of the subcommands whose first positional argument is a shortcut name.
+///
+/// These are the subcommands for which the completion scripts offer stored shortcut names. The list
+/// is derived from the `Cli` definition so new subcommands taking a `name` are picked up automatically.
+fn name_completing_subcommands() -> Vec<String> {
+    Cli::command()
+        .get_subcommands()
+        // `add` takes a new name, so offering existing ones there would only cause collisions
+        .filter(|subcommand| subcommand.get_name() != "add")
+        .filter(|subcommand| {
+            subcommand
+                .get_positionals()
+                .next()
+                .is_some_and(|arg| arg.get_id() == "name")
+        })
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect()
+}
+
+/// A shell that `shell-init` can generate the `projexts cd` integration for.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum ShellKind {
+    /// Add to ~/.bashrc: eval "$(projexts shell-init bash)"
+    Bash,
+    /// Add to ~/.zshrc: eval "$(projexts shell-init zsh)"
+    Zsh,
+    /// Add to ~/.config/fish/config.fish: projexts shell-init fish | source
+    Fish,
+}
+
+/// Returns the shell code that `shell-init` prints, to be sourced from the shell's startup file.
+///
+/// It defines a `projexts` shell function that handles `projexts cd <name>` itself, by changing the
+/// shell's own directory to the one printed by `projexts cd-path <name>`; a program cannot change
+/// the directory of the shell that started it. Every other invocation is passed on to the real
+/// `projexts` executable unchanged.
+fn shell_init(shell: ShellKind) -> &'static str {
+    match shell {
+        ShellKind::Bash | ShellKind::Zsh => {
+            r#"projexts() {
+    if [ "$#" -eq 2 ] && [ "$1" = "cd" ]; then
+        local dir
+        dir="$(command projexts cd-path "$2")" && cd "$dir"
+    else
+        command projexts "$@"
+    fi
+}
+"#
+        }
+        ShellKind::Fish => {
+            r#"function projexts
+    if test (count $argv) -eq 2; and test "$argv[1]" = cd
+        set -l dir (command projexts cd-path $argv[2]); and cd $dir
+    else
+        command projexts $argv
+    end
+end
+"#
+        }
+    }
+}
+
+/// Writes a shell completion script for `projexts` to the given writer.
+///
+/// The static part of the script (subcommands and flags) is generated by `clap_complete`. On top of
+/// that, a small shell-specific snippet is appended that completes shortcut names dynamically: when
+/// the cursor is on the first argument of a subcommand that takes a shortcut name (e.g. `run`,
+/// `remove`, `open`), the script calls `projexts list --names-only` and offers the stored names.
+///
+/// # Arguments
+/// * `shell` - The shell to generate the script for.
+/// * `writer` - Where the script is written, usually stdout.
+///
+/// # Errors
+/// This function will return an error if writing to `writer` fails.
+fn generate_completions(shell: Shell, writer: &mut dyn Write) -> io::Result<()> {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "projexts", &mut script);
+    let mut script = String::from_utf8_lossy(&script).into_owned();
+    let subcommands = name_completing_subcommands();
+
+    match shell {
+        Shell::Bash => {
+            script.push_str(&format!(
+                r#"
+_projexts_with_names() {{
+    if [[ ${{COMP_CWORD}} -eq 2 ]]; then
+        case "${{COMP_WORDS[1]}}" in
+            {})
+                COMPREPLY=( $(compgen -W "$(projexts list --names-only 2>/dev/null)" -- "${{COMP_WORDS[COMP_CWORD]}}") )
+                return 0
+                ;;
+        esac
+    fi
+    _projexts "$@"
+}}
+
+complete -F _projexts_with_names -o bashdefault -o default projexts
+"#,
+                subcommands.join("|")
+            ));
+        }
+        Shell::Zsh => {
+            script.push_str(&format!(
+                r#"
+_projexts_with_names() {{
+    if (( CURRENT == 3 )); then
+        case "${{words[2]}}" in
+            {})
+                local -a names
+                names=(${{(f)"$(projexts list --names-only 2>/dev/null)"}})
+                compadd -a names
+                return
+                ;;
+        esac
+    fi
+    _projexts "$@"
+}}
+
+compdef _projexts_with_names projexts
+"#,
+                subcommands.join("|")
+            ));
+        }
+        Shell::Fish => {
+            script.push_str(&format!(
+                "complete -c projexts -n \"__fish_seen_subcommand_from {}; and test (count (commandline -opc)) -eq 2\" -f -a \"(projexts list --names-only 2>/dev/null)\"\n",
+                subcommands.join(" ")
+            ));
+        }
+        Shell::PowerShell => {
+            // PowerShell only keeps one completer per command, so hook into the generated one
+            let commands: Vec<String> = subcommands
+                .iter()
+                .map(|subcommand| format!("'projexts;{}'", subcommand))
+                .collect();
+            let hook = format!(
+                r#"    if ($command -in @({})) {{
+        $completions += @(projexts list --names-only 2>$null | ForEach-Object {{
+            [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, $_)
+        }})
+    }}
+
+    $completions.Where{{"#,
+                commands.join(", ")
+            );
+            script = script.replacen("    $completions.Where{", &hook, 1);
+        }
+        _ => {}
+    }
+
+    writer.write_all(script.as_bytes())
+}
+
+/// Quotes a string so a POSIX shell reads it back as a single word.
+///
+/// Strings made up only of characters that are never special to the shell are returned unchanged;
+/// anything else is wrapped in single quotes, with embedded single quotes escaped as `'\''`.
+fn shell_quote(s: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "_-./=:,+@%".contains(c);
+    if !s.is_empty() && s.chars().all(is_safe) {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', r"'\''"))
+    }
+}
+
+/// Joins command tokens into a single line that can be pasted into a POSIX shell.
+fn command_line(command: &[String]) -> String {
+    command
+        .iter()
+        .map(|token| shell_quote(token))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Opens the destination for an export: the given file, or stdout if no path is given.
+///
+/// # Errors
+/// This function will return an error if the output file cannot be created.
+fn output_writer(output: Option<&Path>) -> io::Result<Box<dyn Write>> {
+    match output {
+        Some(path) => Ok(Box::new(fs::File::create(path)?)),
+        None => Ok(Box::new(io::stdout())),
+    }
+}
+
+/// Writes the given shortcuts as shell alias definitions.
+///
+/// Shortcuts whose command is a plain executable with arguments (no token contains a path separator)
+/// are written as `alias <name>='<command>'`. Commands that reference paths, and shortcuts in a
+/// namespace, are written as shell functions instead, which forward any extra arguments with `"$@"`. The output can be sourced from
+/// a `.bashrc` or `.zshrc`.
+///
+/// # Arguments
+/// * `shortcuts` - The shortcuts to export.
+/// * `writer` - Where the definitions are written.
+///
+/// # Errors
+/// This function will return an error if writing to `writer` fails.
+fn export_aliases(shortcuts: &[Shortcut], writer: &mut dyn Write) -> io::Result<()> {
+    writeln!(writer, "# Shell aliases generated by projexts")?;
+    for shortcut in shortcuts {
+        let command = command_line(&shortcut.run_command);
+        let has_path = shortcut
+            .run_command
+            .iter()
+            .any(|token| token.contains('/') || token.contains('\\'));
+
+        // Alias names cannot contain '/', so namespaced shortcuts become functions as well
+        if has_path || shortcut.project_name.contains('/') {
+            writeln!(writer, "{}() {{", shortcut.project_name)?;
+            writeln!(writer, "    {} \"$@\"", command)?;
+            writeln!(writer, "}}")?;
+        } else {
+            writeln!(
+                writer,
+                "alias {}={}",
+                shortcut.project_name,
+                shell_quote(&command)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Escapes text for a cell of a Markdown table: pipes would end the cell and line breaks the row.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Writes the given shortcuts as a Markdown table with the columns Name, Command, Description and
+/// Tags, e.g., to document a project's commands in its README.
+///
+/// Commands are written as code, quoted as they would be typed in a shell (see `command_line`).
+/// Pipe characters are escaped so they don't split the table cells.
+///
+/// # Arguments
+/// * `shortcuts` - The shortcuts to export.
+/// * `writer` - Where the table is written.
+///
+/// # Errors
+/// This function will return an error if writing to `writer` fails.
+fn export_as_markdown(shortcuts: &[Shortcut], writer: &mut dyn Write) -> io::Result<()> {
+    writeln!(writer, "| Name | Command | Description | Tags |")?;
+    writeln!(writer, "| --- | --- | --- | --- |")?;
+    for shortcut in shortcuts {
+        let command = command_line(&shortcut.run_command);
+        writeln!(
+            writer,
+            "| {} | {} | {} | {} |",
+            markdown_cell(&shortcut.project_name),
+            if command.is_empty() {
+                String::new()
+            } else {
+                format!("`{}`", markdown_cell(&command))
+            },
+            markdown_cell(shortcut.description.as_deref().unwrap_or("")),
+            markdown_cell(&shortcut.tags.join(", "))
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the given shortcuts as CSV, e.g., to open them in a spreadsheet.
+///
+/// The columns are `name`, `command`, `description`, `tags` and `run_count`. The command is written
+/// as a shell command line (see `command_line`), so `parse_csv_shortcuts` splits it back into the
+/// same tokens, and the tags are joined with semicolons. Fields containing commas, quotes or line
+/// breaks are quoted.
+///
+/// # Arguments
+/// * `shortcuts` - The shortcuts to export.
+/// * `writer` - Where the CSV is written.
+///
+/// # Errors
+/// This function will return an error if writing to `writer` fails.
+fn export_as_csv(shortcuts: &[Shortcut], writer: &mut dyn Write) -> io::Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer.write_record(["name", "command", "description", "tags", "run_count"])?;
+    for shortcut in shortcuts {
+        csv_writer.write_record([
+            shortcut.project_name.as_str(),
+            &command_line(&shortcut.run_command),
+            shortcut.description.as_deref().unwrap_or(""),
+            &shortcut.tags.join(";"),
+            &shortcut.run_count.to_string(),
+        ])?;
+    }
+    csv_writer.flush()
+}
+
+/// Splits a string into words the way a POSIX shell would.
+///
+/// Words are separated by unquoted whitespace. Single quotes preserve everything up to the closing
+/// quote, double quotes preserve everything except backslash escapes, and an unquoted backslash
+/// escapes the next character. This is enough to read back commands written by `shell_quote`.
+fn split_shell_words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                current.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => current.extend(chars.next()),
+                        _ => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                current.extend(chars.next());
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            _ => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+/// Parses a single `alias name='command args'` line into a shortcut.
+///
+/// Returns `None` for lines that are not alias definitions, such as blank lines, comments, and
+/// shell functions.
+fn parse_alias_line(line: &str) -> Option<Shortcut> {
+    let definition = line.trim().strip_prefix("alias ")?;
+    let word = split_shell_words(definition).into_iter().next()?;
+    let (name, command) = word.split_once('=')?;
+    let run_command = split_shell_words(command);
+    if name.is_empty() || run_command.is_empty() {
+        return None;
+    }
+    Some(Shortcut {
+        project_name: name.to_string(),
+        run_command,
+        ..Default::default()
+    })
+}
+
+/// Stores imported shortcuts, either merged into or replacing the existing ones.
+///
+/// When `merge` is true, the imported shortcuts are appended to the stored ones. When `merge` is
+/// false, the stored shortcuts are replaced by the imported ones, except for locked shortcuts (see
+/// `lock_shortcut`), which are kept with a warning. In both cases an imported shortcut whose name is
+/// already taken is reported with a warning and skipped rather than silently overwriting the
+/// existing entry.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were imported.
+/// * `Err(io::Error)` if an error occurs while loading or saving the shortcuts.
+fn store_imported_shortcuts(imported: Vec<Shortcut>, merge: bool) -> io::Result<usize> {
+    let _lock = lock_config()?;
+    let mut shortcuts = load_shortcuts()?;
+    if !merge {
+        shortcuts.retain(|s| s.locked);
+        for shortcut in &shortcuts {
+            eprintln!(
+                "{}",
+                warning(&format!(
+                    "Warning: Keeping locked shortcut '{}'. Unlock it to replace it.",
+                    shortcut.project_name
+                ))
+            );
+        }
+    }
+    let mut count = 0;
+
+    for shortcut in imported {
+        if shortcuts
+            .iter()
+            .any(|s| names_match(&s.project_name, &shortcut.project_name))
+        {
+            eprintln!(
+                "{}",
+                warning(&format!(
+                    "Warning: Skipping '{}': a shortcut with that name already exists.",
+                    shortcut.project_name
+                ))
+            );
+        } else {
+            shortcuts.push(shortcut);
+            count += 1;
+        }
+    }
+    save_shortcuts(&shortcuts)?;
+    Ok(count)
+}
+
+/// Imports shortcuts from a file of shell alias definitions.
+///
+/// Each `alias name='command args'` line becomes a shortcut named `name`. Both single- and
+/// double-quoted values are supported. Comment lines, blank lines, and anything else that is not an
+/// alias definition are skipped.
+///
+/// # Arguments
+/// * `path` - The aliases file to read, e.g. one written by `export-aliases`.
+/// * `merge` - If true, add to the existing shortcuts; otherwise replace them.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were imported.
+/// * `Err(io::Error)` if the file cannot be read or the shortcuts cannot be saved.
+fn import_from_aliases(path: &Path, merge: bool) -> io::Result<usize> {
+    let data = fs::read_to_string(path)?;
+    let imported = data.lines().filter_map(parse_alias_line).collect();
+    store_imported_shortcuts(imported, merge)
+}
+
+/// Parses shortcuts from CSV in the format written by `export_as_csv`.
+///
+/// The first row must name the columns; `name` and `command` are required, while `description`,
+/// `tags` and `run_count` are optional and the columns may come in any order. The command is split
+/// into words like a shell would (see `split_shell_words`) and the tags on semicolons.
+///
+/// Rows with an invalid or empty name, an empty command, or a run count that is not a number are
+/// skipped, and a message for each is collected into the returned report.
+///
+/// # Arguments
+/// * `data` - The CSV text.
+///
+/// # Returns
+/// * `Ok((shortcuts, report))` with the valid shortcuts and a message for each skipped row.
+/// * `Err(io::Error)` if the header row cannot be read or lacks the `name` or `command` column.
+fn parse_csv_shortcuts(data: &str) -> io::Result<(Vec<Shortcut>, Vec<String>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(data.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|header| header.trim() == name);
+    let (Some(name_column), Some(command_column)) = (column("name"), column("command")) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "The CSV must have 'name' and 'command' columns",
+        ));
+    };
+    let description_column = column("description");
+    let tags_column = column("tags");
+    let run_count_column = column("run_count");
+
+    let mut shortcuts = Vec::new();
+    let mut report = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        // Row 1 is the header
+        let row = index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                report.push(format!("Row {}: {}", row, e));
+                continue;
+            }
+        };
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .unwrap_or("")
+                .trim()
+        };
+
+        let name = field(Some(name_column));
+        if let Err(e) = validate_shortcut_name(name) {
+            report.push(format!("Row {}: {}", row, e));
+            continue;
+        }
+        let run_command = split_shell_words(field(Some(command_column)));
+        if run_command.is_empty() {
+            report.push(format!("Row {}: The command of '{}' is empty", row, name));
+            continue;
+        }
+        let run_count = match field(run_count_column) {
+            "" => 0,
+            count => match count.parse() {
+                Ok(count) => count,
+                Err(_) => {
+                    report.push(format!("Row {}: Invalid run count '{}'", row, count));
+                    continue;
+                }
+            },
+        };
+        let description = field(description_column);
+        shortcuts.push(Shortcut {
+            project_name: name.to_string(),
+            run_command,
+            description: (!description.is_empty()).then(|| description.to_string()),
+            tags: field(tags_column)
+                .split(';')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect(),
+            run_count,
+            created_at: Some(now_timestamp()),
+            ..Default::default()
+        });
+    }
+    Ok((shortcuts, report))
+}
+
+/// Imports shortcuts from a CSV file written by `export-csv` (see `parse_csv_shortcuts`).
+///
+/// Each row that cannot be imported is reported with a warning and skipped, and the other rows are
+/// still imported.
+///
+/// # Arguments
+/// * `path` - The CSV file to read.
+/// * `merge` - If true, add to the existing shortcuts; otherwise replace them.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were imported.
+/// * `Err(io::Error)` if the file cannot be read or parsed, or the shortcuts cannot be saved.
+fn import_from_csv(path: &Path, merge: bool) -> io::Result<usize> {
+    let data = fs::read_to_string(path)?;
+    let (imported, report) = parse_csv_shortcuts(&data)?;
+    for message in &report {
+        eprintln!(
+            "{}",
+            warning(&format!("Warning: {}. Skipping the row.", message))
+        );
+    }
+    store_imported_shortcuts(imported, merge)
+}
+
+/// Copies the configuration file to `writer` byte for byte, without parsing or reformatting it.
+///
+/// # Errors
+/// Returns an error with `io::ErrorKind::NotFound` if the configuration file does not exist yet,
+/// or if it cannot be read or `writer` cannot be written to.
+fn show_config(writer: &mut dyn Write) -> io::Result<()> {
+    let path = config_file_path();
+    let mut file = fs::File::open(&path)
+        .map_err(|e| io::Error::new(e.kind(), format!("Cannot open {}: {}", path.display(), e)))?;
+    io::copy(&mut file, writer)?;
+    writer.flush()
+}
+
+/// The differences between two lists of shortcuts, as found by `diff_shortcut_lists`.
+#[derive(Debug, Default)]
+struct ShortcutDiff<'a> {
+    /// Shortcuts only in the second list.
+    added: Vec<&'a Shortcut>,
+    /// Shortcuts only in the first list.
+    removed: Vec<&'a Shortcut>,
+    /// Shortcuts in both lists whose definitions differ, as (first, second) pairs.
+    modified: Vec<(&'a Shortcut, &'a Shortcut)>,
+}
+
+impl ShortcutDiff<'_> {
+    /// Returns `true` if the two lists define the same shortcuts.
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Returns the names of the fields that differ between two definitions of a shortcut.
+///
+/// Usage statistics and timestamps (`run_count`, `last_run_at`, `created_at`, `updated_at`) are
+/// not compared, since they change without the shortcut itself changing.
+fn changed_fields(a: &Shortcut, b: &Shortcut) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if a.run_command != b.run_command {
+        fields.push("command");
+    }
+    if a.description != b.description {
+        fields.push("description");
+    }
+    if a.tags != b.tags {
+        fields.push("tags");
+    }
+    if a.working_dir != b.working_dir {
+        fields.push("working dir");
+    }
+    if a.env != b.env {
+        fields.push("env");
+    }
+    if a.resolve != b.resolve {
+        fields.push("resolve");
+    }
+    if a.pre_run != b.pre_run {
+        fields.push("pre-run");
+    }
+    if a.post_run != b.post_run {
+        fields.push("post-run");
+    }
+    fields
+}
+
+/// Compares two lists of shortcuts by name.
+///
+/// # Arguments
+/// * `a` - The first list, e.g. the current shortcuts.
+/// * `b` - The second list, e.g. the shortcuts of a file about to be imported.
+///
+/// # Returns
+/// The shortcuts only in `b` (added), only in `a` (removed), and in both with a different
+/// definition (modified, see `changed_fields`), each in the order of its list.
+fn diff_shortcut_lists<'a>(a: &'a [Shortcut], b: &'a [Shortcut]) -> ShortcutDiff<'a> {
+    let find =
+        |list: &'a [Shortcut], name: &str| list.iter().find(|s| names_match(&s.project_name, name));
+    let mut diff = ShortcutDiff::default();
+    for old in a {
+        match find(b, &old.project_name) {
+            None => diff.removed.push(old),
+            Some(new) if !changed_fields(old, new).is_empty() => diff.modified.push((old, new)),
+            Some(_) => {}
+        }
+    }
+    diff.added = b
+        .iter()
+        .filter(|new| find(a, &new.project_name).is_none())
+        .collect();
+    diff
+}
+
+/// Reads the shortcuts of a configuration or export file, as TOML if its name ends in `.toml` and
+/// as JSON otherwise.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or parsed.
+fn read_shortcuts_file(path: &Path) -> io::Result<Vec<Shortcut>> {
+    match ConfigFormat::from_path(path) {
+        ConfigFormat::Json => parse_config(&fs::read_to_string(path)?),
+        ConfigFormat::Toml => load_shortcuts_from_toml(path),
+    }
+}
+
+/// Prints how the shortcuts in `other` differ from the stored ones.
+///
+/// Shortcuts only in `other` are printed with `+`, shortcuts missing from it with `-`, and
+/// shortcuts defined differently with `~`, followed by the fields that changed.
+///
+/// # Errors
+/// Returns an error if either list of shortcuts cannot be loaded.
+fn print_shortcut_diff(other: &Path) -> io::Result<()> {
+    let current = load_shortcuts()?;
+    let other = read_shortcuts_file(other)?;
+    let diff = diff_shortcut_lists(&current, &other);
+    if diff.is_empty() {
+        println!("No differences.");
+        return Ok(());
+    }
+    for shortcut in &diff.added {
+        let line = format!(
+            "+ {}: {}",
+            shortcut.project_name,
+            command_line(&shortcut.run_command)
+        );
+        println!("{}", paint(&line, Color::Green, false));
+    }
+    for shortcut in &diff.removed {
+        let line = format!(
+            "- {}: {}",
+            shortcut.project_name,
+            command_line(&shortcut.run_command)
+        );
+        println!("{}", paint(&line, Color::Red, false));
+    }
+    for (old, new) in &diff.modified {
+        let line = format!(
+            "~ {}: {} changed",
+            old.project_name,
+            changed_fields(old, new).join(", ")
+        );
+        println!("{}", paint(&line, Color::Yellow, false));
+        if old.run_command != new.run_command {
+            println!("    - {}", command_line(&old.run_command));
+            println!("    + {}", command_line(&new.run_command));
+        }
+    }
+    println!(
+        "{} added, {} removed, {} modified",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.modified.len()
+    );
+    Ok(())
+}
+
+/// Exports all stored shortcuts to a JSON file.
+///
+/// The file uses the same format as the configuration file, so it can be read back with `import`
+/// on this or another machine.
+///
+/// # Arguments
+/// * `path` - The file to write the shortcuts to. An existing file is overwritten.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were exported.
+/// * `Err(io::Error)` if the shortcuts cannot be loaded or the file cannot be written.
+fn export_to_file(path: &Path) -> io::Result<usize> {
+    let shortcuts = load_shortcuts()?;
+    fs::write(path, serialize_config(&shortcuts)?)?;
+    Ok(shortcuts.len())
+}
+
+/// Exports all stored shortcuts to a TOML file with a `[[shortcuts]]` table for each shortcut, the
+/// same format as a TOML configuration file (see `serialize_toml_config`).
+///
+/// # Arguments
+/// * `path` - The file to write the shortcuts to. An existing file is overwritten.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were exported.
+/// * `Err(io::Error)` if the shortcuts cannot be loaded or the file cannot be written.
+fn export_as_toml(path: &Path) -> io::Result<usize> {
+    let shortcuts = load_shortcuts()?;
+    fs::write(path, serialize_toml_config(&shortcuts)?)?;
+    Ok(shortcuts.len())
+}
+
+/// Imports shortcuts from a TOML file written by `export-toml`.
+///
+/// # Arguments
+/// * `path` - The TOML file to read.
+/// * `merge` - If true, add to the existing shortcuts; otherwise replace them.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were imported.
+/// * `Err(io::Error)` if the file cannot be read or parsed, or the shortcuts cannot be saved.
+fn import_from_toml(path: &Path, merge: bool) -> io::Result<usize> {
+    let imported = load_shortcuts_from_toml(path)?;
+    store_imported_shortcuts(imported, merge)
+}
+
+/// Encodes a single shortcut as a string that can be pasted into chat or documentation and
+/// imported elsewhere with `import-string`.
+///
+/// The shortcut is serialized to JSON and base64-encoded, so the string contains no spaces or
+/// quotes that a shell or chat client could mangle.
+///
+/// # Arguments
+/// * `name` - The name of the shortcut to export.
+///
+/// # Returns
+/// * `Ok(encoded)` with the encoded shortcut.
+/// * `Err(io::Error)` with `io::ErrorKind::NotFound` if no shortcut is named `name`.
+/// * `Err(io::Error)` if the shortcuts cannot be loaded.
+fn export_shortcut_as_string(name: &str) -> io::Result<String> {
+    let shortcuts = load_visible_shortcuts()?;
+    let shortcut = shortcuts
+        .iter()
+        .find(|s| names_match(&s.project_name, name))
+        .ok_or_else(|| shortcut_not_found(name))?;
+    let json = serde_json::to_string(shortcut)?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(json))
+}
+
+/// Decodes a string written by `export_shortcut_as_string` and adds the shortcut with
+/// `add_shortcut`.
+///
+/// The name, command, tags, description, working directory, environment, and hooks are kept. Run
+/// statistics and the locked flag are not, since the shortcut is new to this configuration.
+///
+/// # Arguments
+/// * `encoded` - The encoded shortcut. Surrounding whitespace is ignored.
+///
+/// # Returns
+/// * `Ok(name)` with the name of the imported shortcut.
+/// * `Err(io::Error)` with `io::ErrorKind::InvalidData` if `encoded` is not a valid shortcut string.
+/// * `Err(io::Error)` from `add_shortcut`, e.g. if a shortcut with the same name already exists.
+fn import_shortcut_from_string(encoded: &str) -> io::Result<String> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Not a valid shortcut string: {}", e),
+        )
+    };
+    let json = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| invalid(&e))?;
+    let shortcut: Shortcut = serde_json::from_slice(&json).map_err(|e| invalid(&e))?;
+
+    let options = AddOptions {
+        tags: shortcut.tags,
+        description: shortcut.description,
+        working_dir: shortcut.working_dir,
+        env: shortcut.env.into_iter().collect(),
+        lazy_resolve: shortcut.resolve == ResolveMode::Lazy,
+        pre_run: shortcut.pre_run,
+        post_run: shortcut.post_run,
+        ..Default::default()
+    };
+    add_shortcut(&shortcut.project_name, shortcut.run_command, &options)?;
+    Ok(shortcut.project_name)
+}
+
+/// Imports shortcuts from a JSON file written by `export`.
+///
+/// # Arguments
+/// * `path` - The JSON file to read.
+/// * `merge` - If true, keep the existing shortcuts and add the imported ones, skipping (with a
+///   warning) any whose name is already taken; otherwise replace the existing shortcuts.
+///
+/// # Returns
+/// * `Ok(count)` with the number of shortcuts that were imported.
+/// * `Err(io::Error)` if the file cannot be read or parsed, or the shortcuts cannot be saved.
+fn import_from_file(path: &Path, merge: bool) -> io::Result<usize> {
+    let data = fs::read_to_string(path)?;
+    let imported = parse_config(&data)?;
+    store_imported_shortcuts(imported, merge)
+}
+
+/// Represents a shortcut for a project, including the project's name and the command to run.
+///
+/// This struct is used to store and manage shortcuts for projects, where each shortcut has:
+/// - `project_name`: The name of the project associated with the shortcut.
+/// - `run_command`: A vector of strings representing the command and its arguments to execute the project.
+/// - `tags`: Labels used to group related shortcuts (e.g., `rust`, `work`).
+/// - `description`: An optional note about what the shortcut does.
+/// - `working_dir`: An optional directory the command is run from.
+/// - `env`: Environment variables set for the command.
+/// - `run_count`: How many times the shortcut has been run.
+/// - `last_run_at`: When the shortcut was last run.
+/// - `created_at` and `updated_at`: When the shortcut was added and last changed.
+/// - `pre_run` and `post_run`: Optional commands run before and after the main command.
+/// - `resolve`: Whether relative paths in the command were made absolute when it was added, or are
+///   resolved each time it runs.
+/// - `locked`: Whether the shortcut is protected from being changed or removed.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+struct Shortcut {
+    /// The name of the project associated with the shortcut.
+    project_name: String,
+
+    /// The command (with its arguments) to run the project.
+    run_command: Vec<String>,
+
+    /// Tags used to categorize the shortcut.
+    #[serde(default)]
+    tags: Vec<String>,
+
+    /// A short explanation of what the shortcut does.
+    #[serde(default)]
+    description: Option<String>,
+
+    /// The directory the command is run from. When unset, the command runs in the directory
+    /// projexts was invoked from.
+    #[serde(default)]
+    working_dir: Option<PathBuf>,
+
+    /// Environment variables set for the command, in addition to the ones projexts inherits.
+    #[serde(default)]
+    env: HashMap<String, String>,
+
+    /// The number of times the command has been started with `run`.
+    #[serde(default)]
+    run_count: u64,
+
+    /// When the command was last started, as an ISO-8601 UTC timestamp.
+    #[serde(default)]
+    last_run_at: Option<String>,
+
+    /// When the shortcut was added, as an ISO-8601 UTC timestamp.
+    #[serde(default)]
+    created_at: Option<String>,
+
+    /// When the shortcut was last updated or renamed, as an ISO-8601 UTC timestamp.
+    #[serde(default)]
+    updated_at: Option<String>,
+
+    /// When relative paths in the command are made absolute.
+    #[serde(default)]
+    resolve: ResolveMode,
+
+    /// A command run before the main command, which is skipped if this one fails.
+    #[serde(default)]
+    pre_run: Option<Vec<String>>,
+
+    /// A command run after the main command, whether or not it succeeded.
+    #[serde(default)]
+    post_run: Option<Vec<String>>,
+
+    /// Whether `remove` and `update` refuse to change the shortcut unless forced.
+    #[serde(default)]
+    locked: bool,
+}
+
+/// When the relative paths in a shortcut's command are turned into absolute paths.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ResolveMode {
+    /// Once, when the shortcut is added. Paths that don't exist at that point are kept as typed.
+    #[default]
+    Eager,
+    /// Every time the shortcut runs, relative to the directory projexts is run from. The command
+    /// is stored as typed, so it keeps working for paths created later (e.g., build artifacts) and
+    /// on other machines.
+    Lazy,
+}
+
+/// A command-line interface (CLI) tool to manage project shortcuts.
+///
+/// This struct represents the root of the CLI and serves as an entry point for handling
+/// various commands that interact with project shortcuts (e.g., adding, removing, listing shortcuts).
+///
+/// The CLI tool uses `clap` to parse commands and subcommands, providing a user-friendly way to interact
+/// with the project management functionality.
+#[derive(Parser)]
+#[command(name = "projexts", about = "A CLI tool to manage project shortcuts")]
+struct Cli {
+    /// The subcommand to execute.
+    ///
+    /// This field allows the user to specify which action to take. Each subcommand corresponds to a
+    /// specific operation on the project shortcuts (e.g., adding, removing, listing shortcuts).
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Match shortcut names exactly instead of ignoring case
+    #[arg(long, global = true)]
+    case_sensitive: bool,
+
+    /// Path of the configuration file to use (overrides `PROJEXTS_CONFIG`)
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Format of the configuration file (defaults to TOML for `.toml` files and JSON otherwise)
+    #[arg(long, global = true, value_enum)]
+    config_format: Option<ConfigFormat>,
+
+    /// Profile to use instead of the active one (overrides `PROJEXTS_CONFIG`)
+    #[arg(long, global = true, value_name = "NAME", conflicts_with = "config")]
+    profile: Option<String>,
+
+    /// Disable colored output
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Only print results and errors, not informational messages
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Print tab-separated, machine-readable output for scripts instead of human-readable text
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Print debug messages, such as the configuration file used and the shortcut being run, to
+    /// stderr
+    #[arg(long, global = true)]
+    debug: bool,
+
+    /// Format used by `list` and `info` to print shortcuts
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+}
+
+/// Commands for managing project shortcuts.
+#[derive(Subcommand)]
+enum Commands {
+    /// Add a new shortcut
+    Add {
+        /// Name of the project
+        name: String,
+        /// Command to run the project (supports spaces and arguments)
+        #[arg(last = true)]
+        command: Vec<String>,
+        /// Read the command from stdin, one argument per line
+        #[arg(long = "stdin", conflicts_with = "command")]
+        from_stdin: bool,
+        /// Overwrite an existing shortcut with the same name
+        #[arg(long)]
+        force: bool,
+        /// Tag to categorize the shortcut (can be repeated)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Short explanation of what the shortcut does
+        #[arg(long)]
+        description: Option<String>,
+        /// Directory to run the command from
+        #[arg(long, value_name = "PATH")]
+        working_dir: Option<PathBuf>,
+        /// Environment variable to set for the command (can be repeated)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_env_var)]
+        env: Vec<(String, String)>,
+        /// Store paths in the command as typed and resolve them each time the shortcut runs
+        #[arg(long)]
+        lazy_resolve: bool,
+        /// Expand environment variables such as $HOME in the command now, not when it runs
+        #[arg(long)]
+        expand_env: bool,
+        /// Command to run before the main command, which is skipped if it fails
+        #[arg(long, value_name = "COMMAND", value_parser = parse_command_line)]
+        pre_run: Option<CommandLine>,
+        /// Command to run after the main command, even if it failed
+        #[arg(long, value_name = "COMMAND", value_parser = parse_command_line)]
+        post_run: Option<CommandLine>,
+        /// Save the shortcut in the nearest .projexts.json instead of the global configuration
+        #[arg(long)]
+        local: bool,
+        /// Copy the fields of another shortcut; the command and other options override them
+        #[arg(long, value_name = "SOURCE_NAME")]
+        copy_from: Option<String>,
+    },
+    /// Add a shortcut by answering questions for each field
+    AddInteractive,
+    /// Removes a shortcut
+    Remove {
+        /// Name of the project
+        name: String,
+        /// Show the shortcut and ask for confirmation before removing it
+        #[arg(short, long)]
+        interactive: bool,
+        /// Remove the shortcut even if it is locked
+        #[arg(long)]
+        force: bool,
+    },
+    /// List all shortcuts
+    List {
+        /// Only show shortcuts with this tag (can be repeated to match any of several tags)
+        #[arg(long = "filter-tag", value_name = "TAG")]
+        filter_tags: Vec<String>,
+        /// Only show shortcuts that have all of the --filter-tag tags
+        #[arg(long, requires = "filter_tags")]
+        all_tags: bool,
+        /// Print only the shortcut names, one per line
+        #[arg(long)]
+        names_only: bool,
+        /// Leave out the table header
+        #[arg(long)]
+        no_header: bool,
+        /// Print every field of each shortcut as a block of lines instead of a table
+        #[arg(long)]
+        verbose: bool,
+        /// Print each shortcut on one short line instead of a table
+        #[arg(long, conflicts_with_all = ["verbose", "tree"])]
+        compact: bool,
+        /// Print each shortcut with a template such as "{name}\t{command}"; placeholders are
+        /// {name}, {command}, {description}, {tags}, and {run_count}
+        #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["verbose", "tree", "compact"])]
+        format: Option<String>,
+        /// Print a tree of shortcuts grouped by namespace, or by their first tag if none has one
+        #[arg(long, conflicts_with = "verbose")]
+        tree: bool,
+        /// Only show shortcuts in this namespace (e.g., work for work/frontend)
+        #[arg(long, value_name = "PREFIX")]
+        group: Option<String>,
+        /// Order the shortcuts by this field instead of the order they were added in
+        #[arg(long, value_name = "FIELD")]
+        sort_by: Option<SortField>,
+        /// Reverse the order of the shortcuts
+        #[arg(long)]
+        reverse: bool,
+    },
+    /// Show the shortcuts that have been run the most
+    MostUsed {
+        /// Number of shortcuts to show (defaults to 5)
+        #[arg(short = 'n', long)]
+        count: Option<usize>,
+    },
+    /// Show every detail of a single shortcut
+    Info {
+        /// Name of the project
+        name: String,
+    },
+    /// Search shortcuts by name, tolerating typos
+    Search {
+        /// Name (or approximate name) to search for
+        query: String,
+        /// Maximum number of edits for a name to count as a match
+        #[arg(long, default_value_t = 3)]
+        max_distance: usize,
+    },
+    /// Opens the enclosed folder of the run command
+    Open {
+        /// Name of the project
+        name: String,
+        /// Application to open the folder in (e.g., code), instead of the file manager
+        #[arg(long)]
+        app: Option<String>,
+    },
+    /// Open a terminal in the directory of a project
+    OpenTerminal {
+        /// Name of the project
+        name: String,
+        /// Terminal emulator to use instead of the default one
+        #[arg(long)]
+        app: Option<String>,
+    },
+    /// Open a file from a shortcut
+    OpenFile {
+        /// Name of the project
+        name: String,
+        /// Application to open the file in (e.g., code), instead of the default one
+        #[arg(long)]
+        app: Option<String>,
+    },
+    /// Run a shortcut by name
+    Run {
+        /// Name of the project to run
+        name: String,
+        /// Additional arguments to pass to the command
+        #[arg(last = true)]
+        extra_args: Vec<String>,
+        /// Stop the command if it runs longer than this many seconds
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+        /// Start the command detached from the terminal and print its PID
+        #[arg(long, conflicts_with = "timeout")]
+        background: bool,
+        /// Print the resolved command without running it
+        #[arg(long)]
+        dry_run: bool,
+        /// Value for a {NAME} placeholder in the command (can be repeated)
+        #[arg(long = "set", value_name = "NAME=VALUE", value_parser = parse_env_var)]
+        set_vars: Vec<(String, String)>,
+        /// Also append the command's output to this file, each line prefixed with a timestamp
+        #[arg(long, value_name = "FILE", conflicts_with = "background")]
+        log: Option<PathBuf>,
+        /// Shortcut to run afterwards if the command succeeds
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["background", "repeat"])]
+        on_success: Option<String>,
+        /// Shortcut to run afterwards if the command fails
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["background", "repeat"])]
+        on_failure: Option<String>,
+        /// Print how long the command took once it has finished
+        #[arg(long, conflicts_with = "background")]
+        measure_time: bool,
+        /// Start the command again up to this many times while it exits unsuccessfully
+        #[arg(long, value_name = "N", conflicts_with = "background")]
+        retry: Option<u32>,
+        /// Run the command in a pseudo-terminal, so it keeps colors and progress bars (Unix only)
+        #[arg(long, conflicts_with_all = ["background", "log"])]
+        allocate_pty: bool,
+        /// Seconds to wait before each retry
+        #[arg(long, value_name = "SECONDS", default_value_t = 1, requires = "retry")]
+        retry_delay: u64,
+        /// Start the command with only the shortcut's environment variables, not projexts' own
+        #[arg(long)]
+        no_inherit_env: bool,
+        /// With --no-inherit-env, still pass on this variable (can be repeated)
+        #[arg(long, value_name = "VAR", requires = "no_inherit_env")]
+        inherit_only: Vec<String>,
+        /// Run the command this many times in a row
+        #[arg(long, value_name = "N", conflicts_with_all = ["background", "dry_run"])]
+        repeat: Option<u32>,
+        /// With --repeat, stop after the first run that fails
+        #[arg(long, requires = "repeat")]
+        fail_fast: bool,
+    },
+    /// Run the most recently run shortcut again
+    Last {
+        /// Additional arguments to pass to the command
+        #[arg(last = true)]
+        extra_args: Vec<String>,
+    },
+    /// Run all shortcuts one after another
+    RunAll {
+        /// Only run shortcuts with this tag (can be repeated)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Stop at the first shortcut that fails
+        #[arg(long)]
+        fail_fast: bool,
+    },
+    /// Run several shortcuts at the same time
+    RunParallel {
+        /// Names of the projects to run
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// Maximum number of commands to run at once (defaults to all of them)
+        #[arg(short = 'j', long)]
+        max_jobs: Option<usize>,
+    },
+    /// Copy a shortcut's command to the clipboard
+    Copy {
+        /// Name of the project
+        name: String,
+    },
+    /// Update an existing shortcut
+    Update {
+        /// Name of the project
+        name: String,
+        /// Command to run the project (supports spaces and arguments)
+        #[arg(last = true)]
+        command: Vec<String>,
+        /// Tag to add to the shortcut (can be repeated)
+        #[arg(long = "add-tag")]
+        add_tags: Vec<String>,
+        /// Tag to remove from the shortcut (can be repeated)
+        #[arg(long = "remove-tag")]
+        remove_tags: Vec<String>,
+        /// New description for the shortcut (an empty string removes it)
+        #[arg(long = "set-description", value_name = "TEXT")]
+        description: Option<String>,
+        /// New directory to run the command from
+        #[arg(long, value_name = "PATH")]
+        working_dir: Option<PathBuf>,
+        /// Environment variable to set for the command (can be repeated)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_env_var)]
+        set_env: Vec<(String, String)>,
+        /// Environment variable to stop setting (can be repeated)
+        #[arg(long, value_name = "KEY")]
+        unset_env: Vec<String>,
+        /// Argument to append to the command
+        #[arg(long, value_name = "ARG", allow_hyphen_values = true)]
+        add_arg: Option<String>,
+        /// Argument to remove from the command (its first occurrence)
+        #[arg(long, value_name = "ARG", allow_hyphen_values = true)]
+        remove_arg: Option<String>,
+        /// Argument to insert at a position among the arguments, counting from 0
+        #[arg(long, value_name = "INDEX=ARG", value_parser = parse_insert_arg)]
+        insert_arg_at: Option<(usize, String)>,
+        /// New name for the shortcut
+        #[arg(long, value_name = "NAME")]
+        set_name: Option<String>,
+        /// New command to run before the main command (an empty string removes it)
+        #[arg(long, value_name = "COMMAND", value_parser = parse_command_line)]
+        pre_run: Option<CommandLine>,
+        /// New command to run after the main command (an empty string removes it)
+        #[arg(long, value_name = "COMMAND", value_parser = parse_command_line)]
+        post_run: Option<CommandLine>,
+        /// Update the shortcut even if it is locked
+        #[arg(long)]
+        force: bool,
+    },
+    /// Protect a shortcut from being changed or removed
+    Lock {
+        /// Name of the project
+        name: String,
+    },
+    /// Allow a locked shortcut to be changed and removed again
+    Unlock {
+        /// Name of the project
+        name: String,
+    },
+    /// Rename a shortcut, keeping its command, tags, and description
+    Rename {
+        /// Current name of the project
+        old_name: String,
+        /// New name for the project
+        new_name: String,
+        /// Rename the shortcut even if it is locked
+        #[arg(long)]
+        force: bool,
+    },
+    /// Copy a shortcut under a new name
+    Duplicate {
+        /// Name of the project to copy
+        source: String,
+        /// Name for the copy
+        dest: String,
+    },
+    /// Create a Git repository for a new project and add a shortcut to it
+    GitInit {
+        /// Name of the new shortcut
+        name: String,
+        /// Directory to create the repository in
+        path: PathBuf,
+        /// Name of the initial branch (defaults to Git's init.defaultBranch)
+        #[arg(long, value_name = "BRANCH")]
+        initial_branch: Option<String>,
+    },
+    /// Clone a Git repository and add a shortcut to it
+    GitClone {
+        /// URL of the repository to clone
+        url: String,
+        /// Name of the new shortcut
+        name: String,
+        /// Directory to clone into (defaults to the repository name from the URL)
+        dest: Option<PathBuf>,
+    },
+    /// Add, commit, and push changes to git in directory of the shortcut
+    GitPush {
+        /// Name of the project
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+        /// Commit message
+        #[arg(required_unless_present = "all")]
+        commit_message: Option<String>,
+        /// Push the existing commits of every shortcut whose directory is a Git repository, without committing
+        #[arg(long, conflicts_with_all = ["conventional", "amend"])]
+        all: bool,
+        /// Remote to push to (defaults to origin)
+        #[arg(long)]
+        remote: Option<String>,
+        /// Branch to push (defaults to the current branch)
+        #[arg(long)]
+        branch: Option<String>,
+        /// Overwrite the remote branch, using --force-with-lease
+        #[arg(long)]
+        force: bool,
+        /// Require a Conventional Commits message, such as "fix(cli): handle empty names"
+        #[arg(long)]
+        conventional: bool,
+        /// Amend the last commit instead of creating a new one, then force-push with --force-with-lease
+        #[arg(long)]
+        amend: bool,
+        /// Don't ask for confirmation before amending and force-pushing
+        #[arg(long, requires = "amend")]
+        no_confirm: bool,
+    },
+    /// Commit changes in a project's Git repository without pushing them
+    GitCommit {
+        /// Name of the project
+        name: String,
+        /// Commit message
+        message: String,
+        /// Commit changes to tracked files with `git commit -a` instead of staging everything
+        #[arg(long)]
+        all: bool,
+    },
+    /// Pull changes into a project's Git repository
+    GitPull {
+        /// Name of the project
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+        /// Pull every shortcut whose directory is a Git repository
+        #[arg(long)]
+        all: bool,
+        /// Rebase local commits onto the pulled changes instead of merging
+        #[arg(long)]
+        rebase: bool,
+    },
+    /// Show the branch and changed files of a project's Git repository
+    GitStatus {
+        /// Name of the project
+        name: String,
+    },
+    /// List the branches of a project's Git repository
+    GitBranch {
+        /// Name of the project
+        name: String,
+        /// Include remote-tracking branches
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show recent commits of a project's Git repository
+    GitLog {
+        /// Name of the project
+        name: String,
+        /// Number of commits to show (defaults to 10)
+        #[arg(short = 'n', long)]
+        count: Option<u32>,
+    },
+    /// Show the uncommitted changes in a project's Git repository
+    GitDiff {
+        /// Name of the project
+        name: String,
+        /// Show the staged changes instead of the unstaged ones
+        #[arg(long)]
+        staged: bool,
+    },
+    /// Stash uncommitted changes in a project's Git repository
+    GitStash {
+        /// Name of the project
+        name: String,
+    },
+    /// Restore the most recently stashed changes in a project's Git repository
+    GitStashPop {
+        /// Name of the project
+        name: String,
+    },
+    /// Removes all saved shortcuts
+    Reset {
+        /// Confirm that all shortcuts should be deleted
+        #[arg(long, alias = "force")]
+        confirm: bool,
+    },
+    /// Restore the shortcuts removed by the last reset
+    Restore,
+    /// Export all shortcuts as shell alias definitions
+    ExportAliases {
+        /// File to write the aliases to (defaults to stdout)
+        output: Option<PathBuf>,
+    },
+    /// Export all shortcuts as a Markdown table
+    ExportMarkdown {
+        /// File to write the table to (defaults to stdout)
+        output: Option<PathBuf>,
+    },
+    /// Export all shortcuts as CSV
+    ExportCsv {
+        /// File to write the CSV to (defaults to stdout)
+        output: Option<PathBuf>,
+    },
+    /// Export all shortcuts to a JSON file
+    Export {
+        /// File to write the shortcuts to
+        path: PathBuf,
+    },
+    /// Import shortcuts from a JSON file created by `export`
+    Import {
+        /// File to read the shortcuts from
+        path: PathBuf,
+        /// Keep existing shortcuts instead of replacing them
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Print a single shortcut as a string that can be shared and imported with `import-string`
+    ExportString {
+        /// Name of the shortcut to export
+        name: String,
+    },
+    /// Add a shortcut from a string created by `export-string`
+    ImportString {
+        /// The encoded shortcut
+        encoded: String,
+    },
+    /// Export all shortcuts to a TOML file
+    ExportToml {
+        /// File to write the shortcuts to
+        path: PathBuf,
+    },
+    /// Import shortcuts from a TOML file created by `export-toml`
+    ImportToml {
+        /// File to read the shortcuts from
+        path: PathBuf,
+        /// Keep existing shortcuts instead of replacing them
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Import shortcuts from a file of shell alias definitions
+    ImportAliases {
+        /// File containing `alias name='command'` lines
+        path: PathBuf,
+        /// Keep existing shortcuts instead of replacing them
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Import shortcuts from a CSV file created by `export-csv`
+    ImportCsv {
+        /// File to read the shortcuts from
+        path: PathBuf,
+        /// Keep existing shortcuts instead of replacing them
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Show how the shortcuts in another configuration or export file differ from the current ones
+    Diff {
+        /// The JSON or TOML file to compare with
+        other: PathBuf,
+    },
+    /// Print the path of the configuration file in use, even if it does not exist yet
+    ConfigPath,
+    /// Print the configuration file exactly as it is stored
+    ConfigShow,
+    /// Check the configuration and environment for common problems
+    Doctor,
+    /// Open the configuration file in $EDITOR
+    Edit,
+    /// Check all shortcuts for broken commands, directories, and paths
+    Validate,
+    /// Remove shortcuts whose project path no longer exists
+    Prune {
+        /// Only show which shortcuts would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate the completion script for
+        shell: Shell,
+    },
+    /// List the profiles, marking the active one with *
+    ProfileList,
+    /// Create a profile with no shortcuts
+    ProfileCreate {
+        /// Name of the profile
+        name: String,
+    },
+    /// Delete a profile and all of its shortcuts
+    ProfileDelete {
+        /// Name of the profile
+        name: String,
+    },
+    /// Make a profile the active one (use "default" for the default configuration)
+    ProfileSwitch {
+        /// Name of the profile
+        name: String,
+    },
+    /// Print a shell function that adds `projexts cd <name>`; source it from your shell's startup file
+    ShellInit {
+        /// Shell to generate the function for
+        shell: ShellKind,
+    },
+    /// Print the project directory of a shortcut (used by `projexts cd`)
+    #[command(hide = true)]
+    CdPath {
+        /// Name of the project
+        name: String,
+    },
+}
+
+/// The main entry point for the `projexts` CLI tool.
+///
+/// This function parses the command-line arguments using `Cli::parse()` and dispatches the appropriate
+/// subcommand based on the user's input. Each subcommand corresponds to a specific operation (such as adding,
+/// removing, or listing shortcuts), and the function handles any errors that occur during execution.
+///
+/// It performs the following tasks:
+/// - Adds a new shortcut using the `add_shortcut` function.
+/// - Removes a shortcut using the `remove_shortcut` function.
+/// - Lists all shortcuts using the `list_shortcuts` function.
+/// - Opens the project folder using the `open_project_folder` function.
+/// - Opens a file from a shortcut using the `open_file_from_shortcut` function.
+/// - Runs a shortcut's command using the `run_shortcut` function.
+/// - Updates an existing shortcut using the `update_shortcut` function.
+/// - Pushes changes to Git using the `git_push` function.
+fn main() {
+    let args = Cli::parse();
+    CASE_SENSITIVE.store(args.case_sensitive, Ordering::Relaxed);
+    NO_COLOR.store(args.no_color, Ordering::Relaxed);
+    // Porcelain output leaves out informational messages, like --quiet
+    QUIET.store(args.quiet || args.porcelain, Ordering::Relaxed);
+    PORCELAIN.store(args.porcelain, Ordering::Relaxed);
+    DEBUG.store(args.debug, Ordering::Relaxed);
+    // Whether to color is decided per stream by `color_enabled`, so don't let `colored` second-guess it
+    colored::control::set_override(true);
+    let profile = args.profile.map(|profile| {
+        let root = profiles_root();
+        if let Err(e) = check_profile_exists(&root, &profile) {
+            eprintln!("{}", failure(&e.to_string()));
+            std::process::exit(1);
+        }
+        if profile == DEFAULT_PROFILE {
+            default_config_file_path()
+        } else {
+            profile_path(&root, &profile)
+        }
+    });
+    set_config_override(args.config.or(profile));
+    set_config_format_override(args.config_format);
+    debug_log!(
+        "Configuration file: {:?} ({:?})",
+        config_file_path(),
+        config_format()
+    );
+
+    // Move the config from `~/.projexts_config.json` to the platform default on first launch
+    if config_file_path() == default_config_file_path() {
+        let (old, new) = (legacy_config_file_path(), default_config_file_path());
+        match migrate_config_file(&old, &new) {
+            Ok(true) => log!(
+                "{}",
+                success(&format!("Moved shortcuts from {:?} to {:?}", old, new))
+            ),
+            Ok(false) => {}
+            Err(e) => eprintln!(
+                "{}",
+                failure(&format!("Failed to move shortcuts to {:?}: {}", new, e))
+            ),
+        }
+    }
+
+    match args.command {
+        Commands::Add {
+            name,
+            mut command,
+            from_stdin,
+            force,
+            tags,
+            description,
+            working_dir,
+            env,
+            lazy_resolve,
+            expand_env,
+            pre_run,
+            post_run,
+            local,
+            copy_from,
+        } => {
+            if from_stdin {
+                match read_command_lines(io::stdin().lock()) {
+                    Ok(lines) => command = lines,
+                    Err(e) => {
+                        eprintln!("{}", failure(&format!("Failed to read command: {}", e)));
+                        std::process::exit(1);
+                    }
+                }
+            }
+            if local {
+                // Write to the nearest project file, or start one in the current directory
+                let path = find_local_config().unwrap_or_else(|| {
+                    std::env::current_dir()
+                        .unwrap_or_default()
+                        .join(LOCAL_CONFIG_FILE_NAME)
+                });
+                set_config_override(Some(path));
+            }
+            match &copy_from {
+                Some(source) if command.is_empty() => {
+                    log!("Adding shortcut: {} (copy of {})", name, source)
+                }
+                _ => log!("Adding shortcut: {} -> {:?}", name, command),
+            }
+            let options = AddOptions {
+                force,
+                tags,
+                description,
+                working_dir,
+                env,
+                lazy_resolve,
+                expand_env,
+                pre_run,
+                post_run,
+                copy_from,
+            };
+            let result = add_shortcut(&name, command, &options);
+            report_change(&name, result, "Failed to add shortcut");
+        }
+        Commands::AddInteractive => {
+            let result = prompt_new_shortcut(&mut io::stdin().lock()).and_then(
+                |(name, command, options)| {
+                    add_shortcut(&name, command, &options)?;
+                    Ok(name)
+                },
+            );
+            match result {
+                Ok(name) => log!("{}", success(&format!("Added shortcut '{}'.", name))),
+                Err(e) => {
+                    eprintln!("{}", failure(&format!("Failed to add shortcut: {}", e)));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Remove {
+            name,
+            interactive,
+            force,
+        } => {
+            if interactive {
+                match confirm_removal(&name) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        log!("Removal cancelled.");
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("{}", failure(&format!("Failed to remove shortcut: {}", e)));
+                        std::process::exit(1);
+                    }
+                }
+            }
+            log!("Removing shortcut: {}", name);
+            let result = remove_shortcut(&name, force);
+            report_change(&name, result, "Failed to remove shortcut");
+        }
+        Commands::List {
+            filter_tags,
+            all_tags,
+            names_only,
+            no_header,
+            verbose,
+            compact,
+            format,
+            tree,
+            group,
+            sort_by,
+            reverse,
+        } => {
+            let options = ListOptions {
+                filter_tags,
+                all_tags,
+                no_header,
+                verbose,
+                compact,
+                format,
+                tree,
+                group,
+                sort_by,
+                reverse,
+                output_format: args.output_format,
+            };
+            let result = if names_only {
+                list_names().map(|names| names.iter().for_each(|name| println!("{}", name)))
+            } else {
+                list_shortcuts(&options)
+            };
+            if let Err(e) = result {
+                eprintln!("{}", failure(&format!("Failed to list shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::MostUsed { count } => {
+            let result = load_visible_shortcuts().and_then(|shortcuts| {
+                let top: Vec<Shortcut> = most_used_shortcuts(&shortcuts, count.unwrap_or(5))
+                    .into_iter()
+                    .cloned()
+                    .collect();
+                if top.is_empty() {
+                    println!("No shortcuts have been run yet.");
+                    Ok(())
+                } else {
+                    print_shortcuts_table(&top, &mut io::stdout(), true)
+                }
+            });
+            if let Err(e) = result {
+                eprintln!("{}", failure(&format!("Failed to list shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::Info { name } => {
+            if let Err(e) = info_shortcut(&name, args.output_format) {
+                eprintln!("{}", failure(&format!("Failed to show shortcut: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::Search {
+            query,
+            max_distance,
+        } => {
+            if let Err(e) = print_search_results(&query, max_distance) {
+                eprintln!("{}", failure(&format!("Failed to search shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::Open { name, app } => {
+            if let Err(e) = open_project_folder(&name, app.as_deref()) {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to open project folder: {}", e))
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::OpenTerminal { name, app } => {
+            if let Err(e) = open_terminal(&name, app.as_deref()) {
+                eprintln!("{}", failure(&format!("Failed to open terminal: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::OpenFile { name, app } => {
+            if let Err(e) = open_file_from_shortcut(&name, app.as_deref()) {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to open file from shortcut: {}", e))
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::Run {
+            name,
+            extra_args,
+            timeout,
+            background,
+            dry_run,
+            set_vars,
+            on_success,
+            on_failure,
+            log,
+            repeat,
+            fail_fast,
+            measure_time,
+            no_inherit_env,
+            inherit_only,
+            retry,
+            retry_delay,
+            allocate_pty,
+        } => {
+            if !dry_run {
+                log!(
+                    "Running shortcut '{}' with extra arguments: {:?}",
+                    name,
+                    extra_args
+                );
+            }
+            let options = RunOptions {
+                timeout: timeout.map(Duration::from_secs),
+                background,
+                dry_run,
+                set_vars: set_vars.into_iter().collect(),
+                log,
+                measure_time,
+                no_inherit_env,
+                inherit_only,
+                retries: retry.unwrap_or(0),
+                retry_delay: Duration::from_secs(retry_delay),
+                allocate_pty,
+            };
+            if let Some(times) = repeat {
+                match repeat_shortcut(&name, &extra_args, &options, times, fail_fast) {
+                    Ok((successes, runs)) => {
+                        log!("{} of {} runs succeeded.", successes, runs);
+                        std::process::exit(if successes == times { 0 } else { 1 });
+                    }
+                    Err(e) => {
+                        eprintln!("{}", failure(&format!("Failed to run shortcut: {}", e)));
+                        std::process::exit(1);
+                    }
+                }
+            }
+            let result = run_with_follow_up(
+                &name,
+                extra_args,
+                &options,
+                on_success.as_deref(),
+                on_failure.as_deref(),
+            );
+            match result {
+                // Exit with the command's own exit code so scripts and CI can detect failures
+                Ok(status) => {
+                    let code = status.code().unwrap_or(1);
+                    if porcelain() {
+                        println!("exit\t{}", code);
+                    }
+                    std::process::exit(code)
+                }
+                Err(e) if porcelain() => {
+                    println!("error\t{}", porcelain_field(&e.to_string()));
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("{}", failure(&format!("Failed to run shortcut: {}", e)));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Last { extra_args } => {
+            let result = load_visible_shortcuts().and_then(|shortcuts| {
+                let name = last_run_shortcut(&shortcuts)
+                    .map(|shortcut| shortcut.project_name.clone())
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::NotFound,
+                            "No shortcut has been run yet. Use 'projexts run <name>' first.",
+                        )
+                    })?;
+                log!("Running last shortcut '{}'", highlight(&name));
+                run_shortcut(&name, extra_args, &RunOptions::default())
+            });
+            match result {
+                Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+                Err(e) => {
+                    eprintln!("{}", failure(&format!("Failed to run shortcut: {}", e)));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::RunAll { tags, fail_fast } => {
+            if let Err(e) = run_all_shortcuts(&tags, fail_fast) {
+                eprintln!("{}", failure(&format!("Run failed: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::RunParallel { names, max_jobs } => {
+            let max_jobs = max_jobs.unwrap_or(names.len());
+            if let Err(e) = run_parallel_shortcuts(&names, max_jobs) {
+                eprintln!("{}", failure(&format!("Run failed: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::Copy { name } => {
+            let result = load_visible_shortcuts().and_then(|shortcuts| {
+                let shortcut = shortcuts
+                    .iter()
+                    .find(|s| names_match(&s.project_name, &name))
+                    .ok_or_else(|| shortcut_not_found(&name))?;
+                copy_to_clipboard(&shortcut.run_command)
+            });
+            match result {
+                Ok(()) => log!(
+                    "{}",
+                    success(&format!(
+                        "Copied the command of '{}' to the clipboard.",
+                        name
+                    ))
+                ),
+                Err(e) => {
+                    eprintln!("{}", failure(&format!("Failed to copy command: {}", e)));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Update {
+            name,
+            command,
+            add_tags,
+            remove_tags,
+            description,
+            working_dir,
+            set_env,
+            unset_env,
+            add_arg,
+            remove_arg,
+            insert_arg_at,
+            set_name,
+            pre_run,
+            post_run,
+            force,
+        } => {
+            log!("Updating shortcut: {} -> {:?}", name, command);
+            // Only replace the command if a new one was given, so tag-only updates keep it
+            let new_command = if command.is_empty() {
+                None
+            } else {
+                Some(command)
+            };
+            let options = UpdateOptions {
+                add_tags,
+                remove_tags,
+                description,
+                working_dir,
+                set_env,
+                unset_env,
+                add_arg,
+                remove_arg,
+                insert_arg_at,
+                new_name: None,
+                pre_run,
+                post_run,
+                allow_locked: force,
+            };
+            // A name change on its own is a plain rename; together with other changes, everything
+            // is saved at once by `update_shortcut`
+            let result = match set_name {
+                Some(new_name) if new_command.is_none() && options == UpdateOptions::default() => {
+                    rename_shortcut(&name, &new_name, options.allow_locked).map(|()| {
+                        log!(
+                            "{}",
+                            success(&format!("Shortcut '{}' renamed to '{}'.", name, new_name))
+                        )
+                    })
+                }
+                new_name => update_shortcut(
+                    &name,
+                    new_command,
+                    &UpdateOptions {
+                        new_name,
+                        ..options
+                    },
+                ),
+            };
+            report_change(&name, result, "Failed to update shortcut");
+        }
+        Commands::Lock { name } => match lock_shortcut(&name) {
+            Ok(()) => log!("{}", success(&format!("Shortcut '{}' locked.", name))),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to lock shortcut: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::Unlock { name } => match unlock_shortcut(&name) {
+            Ok(()) => log!("{}", success(&format!("Shortcut '{}' unlocked.", name))),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to unlock shortcut: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::Rename {
+            old_name,
+            new_name,
+            force,
+        } => match rename_shortcut(&old_name, &new_name, force) {
+            Ok(()) => log!(
+                "{}",
+                success(&format!(
+                    "Shortcut '{}' renamed to '{}'.",
+                    old_name, new_name
+                ))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to rename shortcut: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::Duplicate { source, dest } => match duplicate_shortcut(&source, &dest) {
+            Ok(()) => log!(
+                "{}",
+                success(&format!("Shortcut '{}' copied to '{}'.", source, dest))
+            ),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to duplicate shortcut: {}", e))
+                );
+                std::process::exit(1);
+            }
+        },
+        Commands::GitPush {
+            name,
+            commit_message,
+            all,
+            remote,
+            branch,
+            force,
+            conventional,
+            amend,
+            no_confirm,
+        } => {
+            let options = PushOptions {
+                remote,
+                branch,
+                force,
+                conventional,
+                amend,
+            };
+            if all {
+                match git_push_all(&options) {
+                    Ok((pushed, 0)) => {
+                        log!("{}", success(&format!("Pushed {} repositories.", pushed)))
+                    }
+                    Ok((pushed, failed)) => {
+                        eprintln!(
+                            "{}",
+                            failure(&format!(
+                                "Pushed {} repositories, {} failed.",
+                                pushed, failed
+                            ))
+                        );
+                        std::process::exit(1);
+                    }
+                    Err(e) => {
+                        eprintln!("{}", failure(&format!("Failed to push changes: {}", e)));
+                        std::process::exit(1);
+                    }
+                }
+                return;
+            }
+            let name = name.unwrap_or_default();
+            let commit_message = commit_message.unwrap_or_default();
+            if amend {
+                eprintln!(
+                    "{}",
+                    warning("Amending rewrites the last commit, so the push must be forced with --force-with-lease.")
+                );
+                if !no_confirm {
+                    match confirm("Amend the last commit and force-push?", false) {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            log!("Push cancelled.");
+                            return;
+                        }
+                        Err(e) => {
+                            eprintln!("{}", failure(&format!("Failed to push changes: {}", e)));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+            log!("Pushing changes with commit message: {}", commit_message);
+            if let Err(e) = git_push(&name, &commit_message, &options) {
+                eprintln!("{}", failure(&format!("Failed to push changes: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitCommit { name, message, all } => {
+            if let Err(e) = git_commit(&name, &message, all, false) {
+                eprintln!("{}", failure(&format!("Failed to commit changes: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitPull { name, all, rebase } => {
+            if all {
+                match git_pull_all(rebase) {
+                    Ok((pulled, 0)) => {
+                        log!("{}", success(&format!("Pulled {} repositories.", pulled)))
+                    }
+                    Ok((pulled, failed)) => {
+                        eprintln!(
+                            "{}",
+                            failure(&format!(
+                                "Pulled {} repositories, {} failed.",
+                                pulled, failed
+                            ))
+                        );
+                        std::process::exit(1);
+                    }
+                    Err(e) => {
+                        eprintln!("{}", failure(&format!("Failed to pull changes: {}", e)));
+                        std::process::exit(1);
+                    }
+                }
+            } else if let Err(e) = git_pull(name.as_deref().unwrap_or_default(), rebase) {
+                eprintln!("{}", failure(&format!("Failed to pull changes: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitStatus { name } => {
+            if let Err(e) = git_status(&name) {
+                eprintln!("{}", failure(&format!("Failed to show status: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitBranch { name, all } => {
+            if let Err(e) = git_branch(&name, all) {
+                eprintln!("{}", failure(&format!("Failed to list branches: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitLog { name, count } => {
+            if let Err(e) = git_log(&name, count.unwrap_or(10)) {
+                eprintln!("{}", failure(&format!("Failed to show commits: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitInit {
+            name,
+            path,
+            initial_branch,
+        } => {
+            let result = git_init_and_add(&name, &path, initial_branch.as_deref());
+            report_change(&name, result, "Failed to create project");
+        }
+        Commands::GitClone { url, name, dest } => {
+            let result = match dest.or_else(|| repo_name_from_url(&url).map(PathBuf::from)) {
+                Some(dest) => git_clone_and_add(&url, &name, &dest),
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Cannot tell the repository name from '{}'; pass a destination",
+                        url
+                    ),
+                )),
+            };
+            report_change(&name, result, "Failed to clone project");
+        }
+        Commands::GitDiff { name, staged } => {
+            if let Err(e) = git_diff(&name, staged) {
+                eprintln!("{}", failure(&format!("Failed to show changes: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitStash { name } => {
+            if let Err(e) = git_stash(&name) {
+                eprintln!("{}", failure(&format!("Failed to stash changes: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::GitStashPop { name } => {
+            if let Err(e) = git_stash_pop(&name) {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to restore stashed changes: {}", e))
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::Reset { confirm } => {
+            if !confirm {
+                eprintln!(
+                    "{}",
+                    failure("This will delete all shortcuts. Pass --confirm to proceed.")
+                );
+                std::process::exit(1);
+            }
+            if let Err(e) = reset_shortcuts() {
+                eprintln!("{}", failure(&format!("Failed to reset shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::Restore => match restore_shortcuts() {
+            Ok(()) => log!("{}", success("Shortcuts restored.")),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to restore shortcuts: {}", e))
+                );
+                std::process::exit(1);
+            }
+        },
+        Commands::ExportAliases { output } => {
+            let result = load_shortcuts().and_then(|shortcuts| {
+                export_aliases(&shortcuts, &mut output_writer(output.as_deref())?)
+            });
+            if let Err(e) = result {
+                eprintln!("{}", failure(&format!("Failed to export aliases: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::ExportMarkdown { output } => {
+            let result = load_shortcuts().and_then(|shortcuts| {
+                export_as_markdown(&shortcuts, &mut output_writer(output.as_deref())?)
+            });
+            if let Err(e) = result {
+                eprintln!("{}", failure(&format!("Failed to export Markdown: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::ExportCsv { output } => {
+            let result = load_shortcuts().and_then(|shortcuts| {
+                export_as_csv(&shortcuts, &mut output_writer(output.as_deref())?)
+            });
+            if let Err(e) = result {
+                eprintln!("{}", failure(&format!("Failed to export CSV: {}", e)));
+                std::process::exit(1);
+            }
+        }
+        Commands::Export { path } => match export_to_file(&path) {
+            Ok(count) => log!(
+                "{}",
+                success(&format!("Exported {} shortcut(s) to {:?}", count, path))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to export shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::Import { path, merge } => match import_from_file(&path, merge) {
+            Ok(count) => log!(
+                "{}",
+                success(&format!("Imported {} shortcut(s) from {:?}", count, path))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to import shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ImportAliases { path, merge } => match import_from_aliases(&path, merge) {
+            Ok(count) => log!(
+                "{}",
+                success(&format!("Imported {} shortcut(s) from {:?}", count, path))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to import aliases: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ExportString { name } => match export_shortcut_as_string(&name) {
+            Ok(encoded) => println!("{}", encoded),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to export shortcut: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ImportString { encoded } => match import_shortcut_from_string(&encoded) {
+            Ok(name) => log!(
+                "{}",
+                success(&format!("Shortcut '{}' imported successfully.", name))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to import shortcut: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ExportToml { path } => match export_as_toml(&path) {
+            Ok(count) => log!(
+                "{}",
+                success(&format!("Exported {} shortcut(s) to {:?}", count, path))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to export shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ImportToml { path, merge } => match import_from_toml(&path, merge) {
+            Ok(count) => log!(
+                "{}",
+                success(&format!("Imported {} shortcut(s) from {:?}", count, path))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to import shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ImportCsv { path, merge } => match import_from_csv(&path, merge) {
+            Ok(count) => log!(
+                "{}",
+                success(&format!("Imported {} shortcut(s) from {:?}", count, path))
+            ),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to import CSV: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::Diff { other } => {
+            if let Err(e) = print_shortcut_diff(&other) {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to compare shortcuts: {}", e))
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::ConfigPath => {
+            // No trailing newline, so the output can be used as is in `$(projexts config-path)`
+            print!("{}", config_file_path().display());
+            let _ = io::stdout().flush();
+        }
+        Commands::Doctor => {
+            let results = run_doctor();
+            if let Err(e) = print_diagnostics(&results, &mut io::stdout()) {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to print diagnostics: {}", e))
+                );
+                std::process::exit(1);
+            }
+            if results
+                .iter()
+                .any(|result| result.status == DiagnosticStatus::Error)
+            {
+                std::process::exit(1);
+            }
+        }
+        Commands::ConfigShow => {
+            if let Err(e) = show_config(&mut io::stdout()) {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to show configuration: {}", e))
+                );
+                std::process::exit(1);
+            }
+        }
+        Commands::Prune { dry_run } => match prune_shortcuts(dry_run) {
+            Ok(0) => println!("No shortcuts to prune."),
+            Ok(count) if dry_run => println!("{} shortcut(s) would be pruned.", count),
+            Ok(count) => log!("{}", success(&format!("Pruned {} shortcut(s).", count))),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to prune shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::Validate => match load_shortcuts() {
+            Ok(shortcuts) => {
+                let errors = validate_shortcuts(&shortcuts);
+                for shortcut in &shortcuts {
+                    let problems: Vec<&ValidationError> = errors
+                        .iter()
+                        .filter(|e| e.shortcut_name() == shortcut.project_name)
+                        .collect();
+                    if problems.is_empty() {
+                        println!("{} {}", success("✓"), shortcut.project_name);
+                    } else {
+                        println!("{} {}", failure("✗"), shortcut.project_name);
+                        for problem in problems {
+                            println!("    {}", problem);
+                        }
+                    }
+                }
+                if !errors.is_empty() {
+                    eprintln!(
+                        "{}",
+                        failure(&format!("Found {} problem(s).", errors.len()))
+                    );
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to load shortcuts: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::Edit => match edit_config(&editor_command(std::env::var("EDITOR").ok())) {
+            Ok(true) => log!("{}", success("Configuration saved.")),
+            Ok(false) => eprintln!(
+                "{}",
+                warning("Discarded the invalid edits and restored the previous configuration.")
+            ),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to edit configuration: {}", e))
+                );
+                std::process::exit(1);
+            }
+        },
+        Commands::ProfileList => {
+            let root = profiles_root();
+            let active = active_profile(&root).unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+            match list_profiles(&root) {
+                Ok(profiles) => {
+                    for profile in profiles {
+                        let marker = if profile == active { "*" } else { " " };
+                        println!("{} {}", marker, profile);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}", failure(&format!("Failed to list profiles: {}", e)));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::ProfileCreate { name } => match create_profile(&profiles_root(), &name) {
+            Ok(()) => log!("{}", success(&format!("Profile '{}' created.", name))),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to create profile: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ProfileDelete { name } => match delete_profile(&profiles_root(), &name) {
+            Ok(()) => log!("{}", success(&format!("Profile '{}' deleted.", name))),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to delete profile: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ProfileSwitch { name } => match switch_profile(&profiles_root(), &name) {
+            Ok(()) => log!("{}", success(&format!("Switched to profile '{}'.", name))),
+            Err(e) => {
+                eprintln!("{}", failure(&format!("Failed to switch profile: {}", e)));
+                std::process::exit(1);
+            }
+        },
+        Commands::ShellInit { shell } => print!("{}", shell_init(shell)),
+        Commands::CdPath { name } => match find_project_dir(&name) {
+            Ok(dir) => println!("{}", dir.display()),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to find project directory: {}", e))
+                );
+                std::process::exit(1);
+            }
+        },
+        Commands::Completions { shell } => {
+            if let Err(e) = generate_completions(shell, &mut io::stdout()) {
+                eprintln!(
+                    "{}",
+                    failure(&format!("Failed to generate completions: {}", e))
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+// Testing Code
+/////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes the tests that point the process-wide configuration override somewhere.
+    static CONFIG_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// A temporary configuration directory that the configuration override points into, returned
+    /// by `use_temp_config`.
+    ///
+    /// Until it is dropped, no other test can change the override. Dropping it clears the override
+    /// and deletes the directory.
+    struct TempConfig {
+        dir: tempfile::TempDir,
+        _guard: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl std::ops::Deref for TempConfig {
+        type Target = tempfile::TempDir;
+
+        fn deref(&self) -> &tempfile::TempDir {
+            &self.dir
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            set_config_override(None);
+            set_config_format_override(None);
+        }
+    }
+
+    /// Points the process at a fresh, empty configuration file, waiting for any other test using
+    /// one to finish first.
+    ///
+    /// Keep the returned value alive for the duration of the test.
+    fn use_temp_config() -> TempConfig {
+        let guard = CONFIG_TEST_LOCK
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let dir = tempfile::tempdir().unwrap();
+        set_config_override(Some(dir.path().join("config.json")));
+        set_config_format_override(None);
+        TempConfig { dir, _guard: guard }
+    }
+
+    #[test]
+    fn test_config_file_path() {
+        let config = use_temp_config();
+        assert_eq!(config_file_path(), config.path().join("config.json"));
+        // The override applies to the whole process, including other threads
+        let from_thread = std::thread::spawn(config_file_path).join().unwrap();
+        assert_eq!(from_thread, config.path().join("config.json"));
+
+        set_config_override(None);
+        let path = config_file_path();
+        let expected_path = default_config_file_path();
+        if std::env::var_os("PROJEXTS_CONFIG").is_none() {
+            assert_eq!(path, expected_path);
+        }
+    }
+
+    #[test]
+    fn test_profiles() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        assert_eq!(list_profiles(root).unwrap(), vec!["default"]);
+        assert_eq!(active_profile(root), None);
+
+        create_profile(root, "work").unwrap();
+        create_profile(root, "home").unwrap();
+        assert_eq!(
+            create_profile(root, "work").unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        assert!(create_profile(root, "a/b").is_err());
+        assert_eq!(
+            list_profiles(root).unwrap(),
+            vec!["default", "home", "work"]
+        );
+        assert!(
+            parse_config(&fs::read_to_string(profile_path(root, "work")).unwrap())
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(switch_profile(root, "missing").is_err());
+        switch_profile(root, "work").unwrap();
+        assert_eq!(active_profile(root).as_deref(), Some("work"));
+        delete_profile(root, "work").unwrap();
+        assert_eq!(active_profile(root), None);
+        assert_eq!(list_profiles(root).unwrap(), vec!["default", "home"]);
+        assert!(delete_profile(root, "default").is_err());
+        switch_profile(root, "home").unwrap();
+        switch_profile(root, "default").unwrap();
+        assert_eq!(active_profile(root), None);
+    }
+
+    #[test]
+    fn test_migrate_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join(".projexts_config.json");
+        let new = dir.path().join("projexts").join("config.json");
+        assert!(!migrate_config_file(&old, &new).unwrap());
+
+        fs::write(&old, "[]").unwrap();
+        assert!(migrate_config_file(&old, &new).unwrap());
+        assert!(!old.exists());
+        assert_eq!(fs::read_to_string(&new).unwrap(), "[]");
+
+        // An existing config at the new location is never overwritten
+        fs::write(&old, "[{}]").unwrap();
+        assert!(!migrate_config_file(&old, &new).unwrap());
+        assert_eq!(fs::read_to_string(&new).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_load_shortcuts() {
+        let _config = use_temp_config();
+        let result = load_shortcuts();
+        assert!(result.is_ok());
+        let shortcuts = result.unwrap();
+        assert!(shortcuts.is_empty());
+    }
+
+    #[test]
+    fn test_save_shortcuts() {
+        let _config = use_temp_config();
+        let shortcuts = vec![
+            Shortcut {
+                project_name: "proj1".to_string(),
+                run_command: vec!["echo".to_string(), "Hello".to_string()],
+                ..Default::default()
+            },
+            Shortcut {
+                project_name: "proj2".to_string(),
+                run_command: vec!["echo".to_string(), "World".to_string()],
+                ..Default::default()
+            },
+        ];
+        let result = save_shortcuts(&shortcuts);
+        assert!(result.is_ok());
+        let loaded_shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts, loaded_shortcuts);
+    }
+
+    #[test]
+    fn test_write_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("config.json");
+        write_config_file(&path, "first").unwrap();
+        write_config_file(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        // The temporary file was renamed into place, so nothing else is left behind
+        let entries: Vec<PathBuf> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(entries, vec![path]);
+    }
+
+    #[test]
+    fn test_record_run() {
+        let _config = use_temp_config();
+        add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default()).unwrap();
+        let shortcut = &load_shortcuts().unwrap()[0];
+        assert_eq!(shortcut.run_count, 0);
+        assert_eq!(shortcut.last_run_at, None);
+
+        record_run("proj1").unwrap();
+        record_run("proj1").unwrap();
+        let shortcut = &load_shortcuts().unwrap()[0];
+        assert_eq!(shortcut.run_count, 2);
+        let last_run_at = shortcut.last_run_at.as_deref().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(last_run_at).is_ok());
+
+        // Recording a run of a shortcut that doesn't exist changes nothing
+        record_run("missing").unwrap();
+        assert_eq!(load_shortcuts().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_shortcut_timestamps() {
+        let _config = use_temp_config();
+        add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default()).unwrap();
+        let mut shortcuts = load_shortcuts().unwrap();
+        assert!(shortcuts[0].created_at.is_some());
+        assert_eq!(shortcuts[0].updated_at, None);
+
+        // Backdate the creation so an unchanged `created_at` is told apart from a fresh one
+        let created_at = "2020-01-01T00:00:00Z".to_string();
+        shortcuts[0].created_at = Some(created_at.clone());
+        save_shortcuts(&shortcuts).unwrap();
+        let options = UpdateOptions {
+            description: Some("Says hello".to_string()),
+            ..Default::default()
+        };
+        update_shortcut("proj1", None, &options).unwrap();
+        let shortcut = &load_shortcuts().unwrap()[0];
+        assert_eq!(shortcut.created_at, Some(created_at));
+        assert!(shortcut.updated_at.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_set_vars() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let script = format!("echo {{GREETING}}, {{NAME}} > '{}'", out.display());
+        let command = vec!["sh".to_string(), "-c".to_string(), script];
+        add_shortcut("greet", command, &AddOptions::default()).unwrap();
+        // Every placeholder has a value, so nothing is asked on stdin
+        let options = RunOptions {
+            set_vars: HashMap::from([
+                ("GREETING".to_string(), "Hello".to_string()),
+                ("NAME".to_string(), "world".to_string()),
+            ]),
+            ..Default::default()
+        };
+        assert!(run_shortcut("greet", vec![], &options).unwrap().success());
+        assert_eq!(fs::read_to_string(&out).unwrap(), "Hello, world\n");
+        // The stored command keeps its placeholders
+        assert!(load_shortcuts().unwrap()[0].run_command[2].contains("{GREETING}"));
+    }
+
+    #[test]
+    fn test_add_shortcut() {
+        let _config = use_temp_config();
+        let result = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        assert!(result.is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        if shortcuts.len() != 1 {
+            panic!(
+                "Expected 1 shortcut, found {}: {:?}",
+                shortcuts.len(),
+                shortcuts
+            );
+        }
+        assert_eq!(shortcuts[0].project_name, "proj1");
+        assert_eq!(
+            shortcuts[0].run_command,
+            vec!["echo".to_string(), "Hello".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_lazy_resolve() {
+        let _config = use_temp_config();
+        let command = vec!["echo".to_string(), "src".to_string()];
+        let options = AddOptions {
+            lazy_resolve: true,
+            ..Default::default()
+        };
+        add_shortcut("lazy", command.clone(), &options).unwrap();
+        add_shortcut("eager", command, &AddOptions::default()).unwrap();
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[0].resolve, ResolveMode::Lazy);
+        assert_eq!(shortcuts[0].run_command, vec!["echo", "src"]);
+        assert_eq!(shortcuts[1].resolve, ResolveMode::Eager);
+        let src = fs::canonicalize("src").unwrap();
+        assert_eq!(shortcuts[1].run_command[1], src.to_string_lossy());
+
+        let process = build_process(&shortcuts[0], "echo", &shortcuts[0].run_command[1..]);
+        let args: Vec<_> = process.get_args().collect();
+        assert_eq!(args, vec![src.as_os_str()]);
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(expand_env_vars("$HOME/run.sh"), format!("{}/run.sh", home));
+        assert_eq!(expand_env_vars("${HOME}x"), format!("{}x", home));
+        assert_eq!(
+            expand_env_vars("$PROJEXTS_UNSET_VAR/${PROJEXTS_UNSET_VAR}"),
+            "$PROJEXTS_UNSET_VAR/${PROJEXTS_UNSET_VAR}"
+        );
+        assert_eq!(expand_env_vars("cost: $5 ${ $"), "cost: $5 ${ $");
+
+        let _config = use_temp_config();
+        let options = AddOptions {
+            expand_env: true,
+            ..Default::default()
+        };
+        add_shortcut(
+            "now",
+            vec!["echo".to_string(), "$HOME".to_string()],
+            &options,
+        )
+        .unwrap();
+        let command = vec!["echo".to_string(), "$HOME".to_string()];
+        add_shortcut("later", command, &AddOptions::default()).unwrap();
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(
+            shortcuts[0].run_command[1],
+            fs::canonicalize(&home).unwrap().to_string_lossy()
+        );
+        assert_eq!(shortcuts[1].run_command[1], "$HOME");
+        let process = build_process(&shortcuts[1], "echo", &shortcuts[1].run_command[1..]);
+        assert_eq!(process.get_args().collect::<Vec<_>>(), vec![home.as_str()]);
+    }
+
+    #[test]
+    fn test_substitute_placeholders() {
+        let command: Vec<String> = [
+            "git",
+            "checkout",
+            "{BRANCH}",
+            "--tag={VERSION}-{BRANCH}",
+            "{lower}",
+            "{}",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let mut asked = Vec::new();
+        let resolved = substitute_placeholders(&command, |name| {
+            asked.push(name.to_string());
+            Ok(name.to_lowercase())
+        })
+        .unwrap();
+        assert_eq!(asked, vec!["BRANCH", "VERSION"]);
+        assert_eq!(
+            resolved,
+            vec![
+                "git",
+                "checkout",
+                "branch",
+                "--tag=version-branch",
+                "{lower}",
+                "{}"
+            ]
+        );
+
+        let plain = vec!["echo".to_string(), "hi".to_string()];
+        let values = HashMap::from([("BRANCH".to_string(), "main".to_string())]);
+        assert_eq!(resolve_template_args(&plain, &values).unwrap(), plain);
+        let template = vec!["git".to_string(), "{BRANCH}".to_string()];
+        assert_eq!(
+            resolve_template_args(&template, &values).unwrap(),
+            vec!["git", "main"]
+        );
+    }
+
+    #[test]
+    fn test_validate_shortcut_name() {
+        assert!(validate_shortcut_name("my-project_2").is_ok());
+        assert!(validate_shortcut_name("a").is_ok());
+        assert!(validate_shortcut_name(&"a".repeat(MAX_NAME_LENGTH)).is_ok());
+        assert!(validate_shortcut_name(&"a".repeat(MAX_NAME_LENGTH + 1)).is_err());
+        assert!(validate_shortcut_name("").is_err());
+        assert!(validate_shortcut_name("my project").is_err());
+        assert!(validate_shortcut_name("proj.1").is_err());
+        assert!(validate_shortcut_name("caf\u{e9}").is_err());
+        assert!(validate_shortcut_name("-proj").is_err());
+        assert!(validate_shortcut_name("proj-").is_ok());
+
+        let _config = use_temp_config();
+        let err =
+            add_shortcut("bad name", vec!["echo".to_string()], &AddOptions::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default());
+        let err = rename_shortcut("proj1", "-flag", false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_add_duplicate_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        let result = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "World".to_string()],
+            &AddOptions::default(),
+        );
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+
+        let options = AddOptions {
+            force: true,
+            ..Default::default()
+        };
+        let result = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "World".to_string()],
+            &options,
+        );
+        assert!(result.is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts.len(), 1);
+        assert_eq!(
+            shortcuts[0].run_command,
+            vec!["echo".to_string(), "World".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_prompt_new_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("taken", vec!["echo".to_string()], &AddOptions::default());
+        let dir = tempfile::tempdir().unwrap();
+        let answers = format!(
+            "\ntaken\nproj1\nno-such-program-xyz\necho\nhello 'big world'\n/no/such/dir\n{}\nweb, api,\n\n",
+            dir.path().display()
+        );
+        let (name, command, options) = prompt_new_shortcut(&mut answers.as_bytes()).unwrap();
+        assert_eq!(name, "proj1");
+        assert_eq!(command, vec!["echo", "hello", "big world"]);
+        assert_eq!(options.tags, vec!["web", "api"]);
+        assert_eq!(options.description, None);
+        assert_eq!(
+            options.working_dir,
+            Some(fs::canonicalize(dir.path()).unwrap())
+        );
+
+        let err = prompt_new_shortcut(&mut "proj2\n".as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_remove_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        let result = remove_shortcut("proj1", false);
+        assert!(result.is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        assert!(shortcuts.is_empty());
+        let result = remove_shortcut("proj1", false);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_porcelain_field() {
+        assert_eq!(porcelain_field("a\tb\nc"), "a b c");
+    }
+
+    #[test]
+    fn test_case_insensitive_lookup() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "Proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        let result = add_shortcut("PROJ1", vec!["echo".to_string()], &AddOptions::default());
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        let result = remove_shortcut("proj1", false);
+        assert!(result.is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        assert!(shortcuts.is_empty());
+    }
+
+    #[test]
+    fn test_list_shortcuts() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        let _ = add_shortcut(
+            "proj2",
+            vec!["echo".to_string(), "World".to_string()],
+            &AddOptions::default(),
+        );
+        let result = list_shortcuts(&ListOptions::default());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_print_shortcuts_table() {
+        NO_COLOR.store(true, Ordering::Relaxed);
+        let shortcuts = vec![
+            Shortcut {
+                project_name: "build".to_string(),
+                run_command: vec!["cargo".to_string(), "build".to_string()],
+                ..Default::default()
+            },
+            Shortcut {
+                project_name: "hi".to_string(),
+                run_command: vec!["echo".to_string(), "hello world".to_string()],
+                ..Default::default()
+            },
+        ];
+        let mut output = Vec::new();
+        print_shortcuts_table(&shortcuts, &mut output, true).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("NAME  | COMMAND"));
+        assert!(lines[1].starts_with("build | cargo build"));
+        assert!(lines[2].starts_with("hi    | echo 'hello world'"));
+
+        let mut output = Vec::new();
+        print_shortcuts_table(&shortcuts, &mut output, false).unwrap();
+        assert!(String::from_utf8(output).unwrap().starts_with("build | "));
+    }
+
+    #[test]
+    fn test_info_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default());
+        for format in [OutputFormat::Text, OutputFormat::Json, OutputFormat::Tsv] {
+            assert!(info_shortcut("proj1", format).is_ok());
+        }
+        let result = info_shortcut("missing", OutputFormat::Text);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_write_tsv_line() {
+        let shortcut = Shortcut {
+            project_name: "hi".to_string(),
+            run_command: vec!["echo".to_string(), "hello world".to_string()],
+            tags: vec!["a".to_string(), "b".to_string()],
+            description: Some("Says\thello".to_string()),
+            working_dir: Some(PathBuf::from("/tmp")),
+            env: HashMap::from([("LOG".to_string(), "debug".to_string())]),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        write_tsv_line(&shortcut, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "hi\techo 'hello world'\ta,b\tSays hello\t/tmp\tLOG=debug\t0\t\n"
+        );
+    }
+
+    #[test]
+    fn test_color_enabled() {
+        assert!(!color_enabled(false));
+        if std::env::var_os("NO_COLOR").is_none() && std::env::var_os("CLICOLOR").is_none() {
+            NO_COLOR.store(true, Ordering::Relaxed);
+            assert!(!color_enabled(true));
+        }
+    }
+
+    #[test]
+    fn test_editor_command() {
+        assert_eq!(
+            editor_command(Some("code --wait".to_string())),
+            vec!["code".to_string(), "--wait".to_string()]
+        );
+        let fallback = if cfg!(target_os = "windows") {
+            "notepad"
+        } else {
+            "vi"
+        };
+        assert_eq!(editor_command(None), vec![fallback.to_string()]);
+        assert_eq!(
+            editor_command(Some("  ".to_string())),
+            vec![fallback.to_string()]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_config() {
+        let config = use_temp_config();
+        add_shortcut("json", vec!["ls".to_string()], &AddOptions::default()).unwrap();
+        assert!(edit_config(&["true".to_string()]).unwrap());
+
+        set_config_override(Some(config.path().join("config.toml")));
+        add_shortcut("toml", vec!["ls".to_string()], &AddOptions::default()).unwrap();
+        assert!(edit_config(&["true".to_string()]).unwrap());
+        assert_eq!(load_shortcuts().unwrap()[0].project_name, "toml");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_shortcuts() {
+        let valid = Shortcut {
+            project_name: "ok".to_string(),
+            run_command: vec!["sh".to_string(), "-c".to_string(), "true".to_string()],
+            ..Default::default()
+        };
+        let broken = Shortcut {
+            project_name: "broken".to_string(),
+            run_command: vec!["projexts-no-such-program".to_string()],
+            working_dir: Some(PathBuf::from("/no/such/dir")),
+            env: HashMap::from([("DATA".to_string(), "/no/such/file".to_string())]),
+            ..Default::default()
+        };
+        let empty = Shortcut {
+            project_name: "empty".to_string(),
+            ..Default::default()
+        };
+        let errors = validate_shortcuts(&[valid, broken, empty]);
+        assert_eq!(errors.len(), 4);
+        assert!(errors[..3].iter().all(|e| e.shortcut_name() == "broken"));
+        assert_eq!(
+            errors[3],
+            ValidationError::EmptyCommand {
+                name: "empty".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_prune_shortcuts() {
+        let config = use_temp_config();
+        let existing = config.path().to_string_lossy().to_string();
+        let missing = config.path().join("gone").to_string_lossy().to_string();
+        let _ = add_shortcut("here", vec![existing], &AddOptions::default());
+        let _ = add_shortcut("gone", vec![missing], &AddOptions::default());
+        let _ = add_shortcut("nopath", vec!["cargo".to_string()], &AddOptions::default());
+        assert_eq!(prune_shortcuts(true).unwrap(), 1);
+        assert_eq!(load_shortcuts().unwrap().len(), 3);
+        assert_eq!(prune_shortcuts(false).unwrap(), 1);
+        assert_eq!(list_names().unwrap(), vec!["here", "nopath"]);
+    }
+
+    #[test]
+    fn test_format_shortcut() {
+        let shortcut = Shortcut {
+            project_name: "build".to_string(),
+            run_command: vec!["cargo".to_string(), "build".to_string()],
+            tags: vec!["rust".to_string(), "cli".to_string()],
+            run_count: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_shortcut(
+                &shortcut,
+                "{name}\t{command} [{tags}] {run_count}x{description}"
+            )
+            .unwrap(),
+            "build\tcargo build [rust,cli] 3x"
+        );
+        assert_eq!(format_shortcut(&shortcut, "{ {name").unwrap(), "{ {name");
+        let result = format_shortcut(&shortcut, "{nmae}");
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_shortcut_formatter() {
+        let shortcut = Shortcut {
+            project_name: "build".to_string(),
+            run_command: vec!["cargo".to_string(), "x".repeat(100)],
+            tags: vec!["rust".to_string()],
+            ..Default::default()
+        };
+        let formatter = ShortcutFormatter::default();
+        let compact = formatter.compact(&shortcut);
+        assert!(compact.starts_with("build: cargo xxx"));
+        assert_eq!(compact.chars().count(), 80);
+
+        let verbose = formatter.verbose(&shortcut);
+        assert!(verbose.contains("Command:\n  cargo\n"));
+        assert!(verbose.contains("Tags:        rust\n"));
+        assert!(verbose.contains("Created:     -\n"));
+    }
+
+    #[test]
+    fn test_print_shortcuts_tree() {
+        let shortcuts: Vec<Shortcut> =
+            [("a", vec!["web"]), ("b", vec![]), ("c", vec!["web", "cli"])]
+                .into_iter()
+                .map(|(name, tags)| Shortcut {
+                    project_name: name.to_string(),
+                    run_command: vec!["make".to_string()],
+                    tags: tags.into_iter().map(str::to_string).collect(),
+                    ..Default::default()
+                })
+                .collect();
+        let groups = group_shortcuts_by_tag(&shortcuts);
+        assert_eq!(
+            groups.keys().copied().collect::<Vec<_>>(),
+            vec!["web", "untagged"]
+        );
+
+        let mut output = Vec::new();
+        print_shortcuts_tree(&shortcuts, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "web\n├── a  make\n└── c  make\nuntagged\n└── b  make\n"
+        );
+    }
+
+    #[test]
+    fn test_namespaces() {
+        assert!(validate_shortcut_name("work/frontend").is_ok());
+        assert!(validate_shortcut_name("/work").is_err());
+        assert!(validate_shortcut_name("work/").is_err());
+        assert!(validate_shortcut_name("work//api").is_err());
+        assert!(in_namespace("work/frontend", "work"));
+        assert!(in_namespace("work/api/server", "work/api/"));
+        assert!(!in_namespace("workshop", "work"));
+        assert!(!in_namespace("work", "work"));
+
+        let shortcuts: Vec<Shortcut> = ["work/frontend", "tools", "work/api/server", "work/api/db"]
+            .into_iter()
+            .map(|name| Shortcut {
+                project_name: name.to_string(),
+                run_command: vec!["make".to_string()],
+                ..Default::default()
+            })
+            .collect();
+        let mut output = Vec::new();
+        print_shortcuts_tree(&shortcuts, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "work\n├── frontend  make\n└── api\n    ├── server  make\n    └── db  make\ntools  make\n"
+        );
+    }
+
+    #[test]
+    fn test_filter_shortcuts_by_tags() {
+        let shortcuts: Vec<Shortcut> = [
+            ("a", vec!["rust", "cli"]),
+            ("b", vec!["rust"]),
+            ("c", vec![]),
+        ]
+        .into_iter()
+        .map(|(name, tags)| Shortcut {
+            project_name: name.to_string(),
+            tags: tags.into_iter().map(str::to_string).collect(),
+            ..Default::default()
+        })
+        .collect();
+        let names = |tags: &[&str], all: bool| -> Vec<String> {
+            let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+            filter_shortcuts_by_tags(&shortcuts, &tags, all)
+                .iter()
+                .map(|s| s.project_name.clone())
+                .collect()
+        };
+        assert_eq!(names(&[], false), vec!["a", "b", "c"]);
+        assert_eq!(names(&["cli", "rust"], false), vec!["a", "b"]);
+        assert_eq!(names(&["cli", "rust"], true), vec!["a"]);
+    }
+
+    #[test]
+    fn test_sort_shortcuts_by() {
+        let mut shortcuts: Vec<Shortcut> = [("beta", 3, None), ("Alpha", 1, Some("2024"))]
+            .into_iter()
+            .map(|(name, run_count, created_at)| Shortcut {
+                project_name: name.to_string(),
+                run_count,
+                created_at: created_at.map(str::to_string),
+                ..Default::default()
+            })
+            .collect();
+        sort_shortcuts_by(&mut shortcuts, SortField::Name);
+        assert_eq!(shortcuts[0].project_name, "Alpha");
+        sort_shortcuts_by(&mut shortcuts, SortField::CreatedAt);
+        assert_eq!(shortcuts[0].project_name, "beta");
+        sort_shortcuts_by(&mut shortcuts, SortField::RunCount);
+        assert_eq!(shortcuts[0].run_count, 1);
+    }
+
+    #[test]
+    fn test_most_used_shortcuts() {
+        let shortcuts: Vec<Shortcut> = [("a", 2), ("b", 0), ("c", 5), ("d", 2)]
+            .into_iter()
+            .map(|(name, run_count)| Shortcut {
+                project_name: name.to_string(),
+                run_count,
+                ..Default::default()
+            })
+            .collect();
+        let names: Vec<&str> = most_used_shortcuts(&shortcuts, 5)
+            .iter()
+            .map(|s| s.project_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["c", "a", "d"]);
+        assert_eq!(most_used_shortcuts(&shortcuts, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_last_run_shortcut() {
+        let shortcuts: Vec<Shortcut> = [
+            ("a", Some("2024-05-01T10:00:00Z")),
+            ("b", Some("2024-06-01T09:00:00Z")),
+            ("c", None),
+        ]
+        .into_iter()
+        .map(|(name, last_run_at)| Shortcut {
+            project_name: name.to_string(),
+            last_run_at: last_run_at.map(str::to_string),
+            ..Default::default()
+        })
+        .collect();
+        assert_eq!(last_run_shortcut(&shortcuts).unwrap().project_name, "b");
+        assert!(last_run_shortcut(&shortcuts[2..]).is_none());
+    }
+
+    #[test]
+    fn test_list_names() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default());
+        let _ = add_shortcut("proj2", vec!["echo".to_string()], &AddOptions::default());
+        let names = list_names().unwrap();
+        assert_eq!(names, vec!["proj1".to_string(), "proj2".to_string()]);
+    }
+
+    #[test]
+    fn test_generate_completions() {
+        let subcommands = name_completing_subcommands();
+        assert!(subcommands.contains(&"run".to_string()));
+        assert!(!subcommands.contains(&"add".to_string()));
+        assert!(!subcommands.contains(&"list".to_string()));
+
+        let mut output = Vec::new();
+        generate_completions(Shell::Bash, &mut output).unwrap();
+        let script = String::from_utf8(output).unwrap();
+        assert!(script.contains("projexts list --names-only"));
+    }
+
+    #[test]
+    fn test_shell_init() {
+        for shell in [ShellKind::Bash, ShellKind::Zsh, ShellKind::Fish] {
+            let script = shell_init(shell);
+            assert!(script.contains("command projexts cd-path"));
+            assert!(script.contains("command projexts "));
+        }
+        assert!(shell_init(ShellKind::Fish).starts_with("function projexts"));
+        assert!(Cli::try_parse_from(["projexts", "cd-path", "proj1"]).is_ok());
+    }
+
+    #[test]
+    fn test_export_aliases() {
+        let shortcuts = vec![
+            Shortcut {
+                project_name: "build".to_string(),
+                run_command: vec!["cargo".to_string(), "build".to_string()],
+                ..Default::default()
+            },
+            Shortcut {
+                project_name: "script".to_string(),
+                run_command: vec!["/home/me/run.sh".to_string(), "it's".to_string()],
+                ..Default::default()
+            },
+        ];
+        let mut output = Vec::new();
+        export_aliases(&shortcuts, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("alias build='cargo build'\n"));
+        assert!(output.contains("script() {\n    /home/me/run.sh 'it'\\''s' \"$@\"\n}\n"));
+    }
+
+    #[test]
+    fn test_export_as_markdown() {
+        let shortcuts = vec![Shortcut {
+            project_name: "logs".to_string(),
+            run_command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "cat log | less".to_string(),
+            ],
+            description: Some("Show the log".to_string()),
+            tags: vec!["ops".to_string(), "debug".to_string()],
+            ..Default::default()
+        }];
+        let mut output = Vec::new();
+        export_as_markdown(&shortcuts, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "| Name | Command | Description | Tags |\n\
+             | --- | --- | --- | --- |\n\
+             | logs | `sh -c 'cat log \\| less'` | Show the log | ops, debug |\n"
+        );
+    }
+
+    #[test]
+    fn test_export_as_csv() {
+        let shortcuts = vec![Shortcut {
+            project_name: "build".to_string(),
+            run_command: vec!["cargo".to_string(), "build".to_string()],
+            description: Some("Build, then wait".to_string()),
+            tags: vec!["rust".to_string(), "ci".to_string()],
+            run_count: 3,
+            ..Default::default()
+        }];
+        let mut output = Vec::new();
+        export_as_csv(&shortcuts, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "name,command,description,tags,run_count\n\
+             build,cargo build,\"Build, then wait\",rust;ci,3\n"
+        );
+
+        // Tokens with spaces, quotes and backslashes survive a round trip through import-csv
+        let shortcuts = vec![Shortcut {
+            project_name: "greet".to_string(),
+            run_command: vec![
+                "echo".to_string(),
+                "hello world".to_string(),
+                "it's".to_string(),
+                r"C:\Users\me".to_string(),
+            ],
+            ..Default::default()
+        }];
+        let mut output = Vec::new();
+        export_as_csv(&shortcuts, &mut output).unwrap();
+        let (parsed, report) = parse_csv_shortcuts(&String::from_utf8(output).unwrap()).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(parsed[0].run_command, shortcuts[0].run_command);
+    }
+
+    #[test]
+    fn test_parse_csv_shortcuts() {
+        let data = "name,command,description,tags,run_count\n\
+                    build,cargo build,\"Build, then wait\",rust;ci,3\n\
+                    ,echo,,,\n\
+                    empty,,,,\n\
+                    greet,echo 'hello world',,,x\n\
+                    greet,echo 'hello world',,,\n";
+        let (shortcuts, report) = parse_csv_shortcuts(data).unwrap();
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].run_command, vec!["cargo", "build"]);
+        assert_eq!(
+            shortcuts[0].description.as_deref(),
+            Some("Build, then wait")
+        );
+        assert_eq!(shortcuts[0].tags, vec!["rust", "ci"]);
+        assert_eq!(shortcuts[0].run_count, 3);
+        assert_eq!(shortcuts[1].run_command, vec!["echo", "hello world"]);
+        assert_eq!(report.len(), 3);
+        assert!(report[0].starts_with("Row 3:"));
+        assert!(report[2].contains("'x'"));
+        assert!(parse_csv_shortcuts("name,description\n").is_err());
+    }
+
+    #[test]
+    fn test_split_shell_words() {
+        assert_eq!(
+            split_shell_words(r#"cargo run -- "a b" 'c d' e\ f 'it'\''s'"#),
+            vec!["cargo", "run", "--", "a b", "c d", "e f", "it's"]
+        );
+        assert!(split_shell_words("   ").is_empty());
+    }
+
+    #[test]
+    fn test_import_from_aliases() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("build", vec!["make".to_string()], &AddOptions::default());
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(
+            file.path(),
+            "# comment\nalias build='cargo build'\nalias ll=\"ls -la\"\nexport FOO=1\n",
+        )
+        .unwrap();
+
+        let count = import_from_aliases(file.path(), true).unwrap();
+        assert_eq!(count, 1);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].run_command, vec!["make".to_string()]);
+        assert_eq!(
+            shortcuts[1].run_command,
+            vec!["ls".to_string(), "-la".to_string()]
+        );
+
+        let count = import_from_aliases(file.path(), false).unwrap();
+        assert_eq!(count, 2);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(
+            shortcuts[0].run_command,
+            vec!["cargo".to_string(), "build".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_export_and_import_file() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default());
+        let _ = add_shortcut("proj2", vec!["ls".to_string()], &AddOptions::default());
+        let file = tempfile::NamedTempFile::new().unwrap();
+        assert_eq!(export_to_file(file.path()).unwrap(), 2);
+
+        let other = tempfile::tempdir().unwrap();
+        set_config_override(Some(other.path().join("config.json")));
+        let _ = add_shortcut("proj1", vec!["pwd".to_string()], &AddOptions::default());
+        assert_eq!(import_from_file(file.path(), true).unwrap(), 1);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].run_command, vec!["pwd".to_string()]);
+
+        assert_eq!(import_from_file(file.path(), false).unwrap(), 2);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[0].run_command, vec!["echo".to_string()]);
+    }
+
+    #[test]
+    fn test_git_push_amend_flags() {
+        let cli = Cli::try_parse_from(["projexts", "git-push", "p", "msg", "--no-confirm"]);
+        assert!(cli.is_err());
+        let cli = Cli::try_parse_from(["projexts", "git-push", "--all", "--amend"]);
+        assert!(cli.is_err());
+        let cli = Cli::try_parse_from([
+            "projexts",
+            "git-push",
+            "p",
+            "msg",
+            "--amend",
+            "--no-confirm",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::GitPush {
+                amend, no_confirm, ..
+            } => assert!(amend && no_confirm),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_validate_conventional_commit() {
+        assert!(validate_conventional_commit("feat: add doctor"));
+        assert!(validate_conventional_commit("fix(cli): handle empty names"));
+        assert!(validate_conventional_commit(
+            "docs(readme): explain --stdin\n\nLonger body."
+        ));
+        assert!(!validate_conventional_commit("Add doctor"));
+        assert!(!validate_conventional_commit("feature: add doctor"));
+        assert!(!validate_conventional_commit("feat(): add doctor"));
+        assert!(!validate_conventional_commit("feat:add doctor"));
+        assert!(!validate_conventional_commit("feat: "));
+        assert!(!validate_conventional_commit(&format!(
+            "feat: {}",
+            "a".repeat(73)
+        )));
+
+        let _config = use_temp_config();
+        let options = PushOptions {
+            conventional: true,
+            ..Default::default()
+        };
+        let error = git_push("missing", "update stuff", &options).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        let _config = use_temp_config();
+        let home = dirs::home_dir().unwrap();
+        let home = home.to_string_lossy();
+        assert_eq!(
+            expand_tilde("~/Documents/project/run.sh"),
+            format!("{}/Documents/project/run.sh", home)
+        );
+        assert_eq!(expand_tilde("~"), home);
+        assert_eq!(expand_tilde("~other/run.sh"), "~other/run.sh");
+        assert_eq!(expand_tilde("a~/b"), "a~/b");
+
+        add_shortcut(
+            "script",
+            vec!["~/Documents/project/run.sh".to_string()],
+            &AddOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            load_shortcuts().unwrap()[0].run_command,
+            vec![format!("{}/Documents/project/run.sh", home)]
+        );
+    }
+
+    #[test]
+    fn test_read_command_lines() {
+        let input = "cargo\r\nbuild\n\n--features\na b\n";
+        assert_eq!(
+            read_command_lines(input.as_bytes()).unwrap(),
+            vec!["cargo", "build", "--features", "a b"]
+        );
+
+        let cli = Cli::try_parse_from(["projexts", "add", "x", "--stdin", "--", "ls"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_shortcut_string_round_trip() {
+        let _config = use_temp_config();
+        let options = AddOptions {
+            tags: vec!["web".to_string()],
+            description: Some("Start the server".to_string()),
+            lazy_resolve: true,
+            ..Default::default()
+        };
+        add_shortcut(
+            "serve",
+            vec!["npm".to_string(), "start".to_string()],
+            &options,
+        )
+        .unwrap();
+        let encoded = export_shortcut_as_string("serve").unwrap();
+        assert!(!encoded.contains(' '));
+
+        remove_shortcut("serve", false).unwrap();
+        assert_eq!(import_shortcut_from_string(&encoded).unwrap(), "serve");
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[0].run_command, vec!["npm", "start"]);
+        assert_eq!(shortcuts[0].tags, vec!["web"]);
+        assert_eq!(
+            shortcuts[0].description.as_deref(),
+            Some("Start the server")
+        );
+
+        let error = import_shortcut_from_string("not base64!").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let _config = use_temp_config();
+        let names = ["my-project", "website", "api"];
+        assert_eq!(
+            suggestion::closest_match("my-projct", names.into_iter()),
+            Some("my-project")
+        );
+        assert_eq!(
+            suggestion::closest_match("WEBSIT", names.into_iter()),
+            Some("website")
+        );
+        assert_eq!(
+            suggestion::closest_match("something-else", names.into_iter()),
+            None
+        );
+
+        add_shortcut("website", vec!["ls".to_string()], &AddOptions::default()).unwrap();
+        let error = remove_shortcut("websit", false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No shortcut found with name 'websit'. Did you mean 'website'?"
+        );
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("proj", ""), 4);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("projexts", "projexts"), 0);
+    }
+
+    #[test]
+    fn test_search_shortcuts() {
+        let shortcuts = vec![
+            Shortcut {
+                project_name: "frontend".to_string(),
+                ..Default::default()
+            },
+            Shortcut {
+                project_name: "backend".to_string(),
+                ..Default::default()
+            },
+            Shortcut {
+                project_name: "Front".to_string(),
+                ..Default::default()
+            },
+        ];
+        let results = search_shortcuts(&shortcuts, "front", 3);
+        let names: Vec<&str> = results
+            .iter()
+            .map(|(s, _)| s.project_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Front", "frontend"]);
+        assert_eq!(results[0].1, 0);
+        assert!(search_shortcuts(&shortcuts, "database", 3).is_empty());
+    }
+
+    #[test]
+    fn test_open_project_folder() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("proj1", vec![".".to_string()], &AddOptions::default());
+        let result = open_project_folder("proj1", None);
+        assert!(result.is_ok());
+        if cfg!(target_os = "linux") {
+            assert!(open_project_folder("proj1", Some("xdg-open")).is_ok());
+        }
+        let err = open_project_folder("missing", None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_open_terminal() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let options = AddOptions {
+            working_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        add_shortcut("proj1", vec!["echo".to_string()], &options).unwrap();
+        assert!(open_terminal("proj1", Some("true")).is_ok());
+        assert!(open_terminal("proj1", Some("")).is_err());
+        assert!(open_terminal("missing", Some("true")).is_err());
+    }
+
+    #[test]
+    fn test_open_command() {
+        if cfg!(target_os = "linux") {
+            assert_eq!(open_command(None).unwrap(), vec!["xdg-open"]);
+            assert_eq!(
+                open_command(Some("code --new-window")).unwrap(),
+                vec!["code", "--new-window"]
+            );
+            assert!(open_command(Some(" ")).is_err());
+        } else if cfg!(target_os = "macos") {
+            assert_eq!(
+                open_command(Some("Xcode")).unwrap(),
+                vec!["open", "-a", "Xcode"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        let result = run_shortcut("proj1", vec![], &RunOptions::default());
+        assert!(result.unwrap().success());
+        let shortcut = &load_shortcuts().unwrap()[0];
+        assert_eq!(shortcut.run_count, 1);
+        assert!(shortcut.last_run_at.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_exit_status() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "fails",
+            vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            &AddOptions::default(),
+        );
+        let status = run_shortcut("fails", vec![], &RunOptions::default()).unwrap();
+        assert_eq!(status.code(), Some(3));
+        let result = run_shortcut("missing", vec![], &RunOptions::default());
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_run_shortcut_background() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        let options = RunOptions {
+            background: true,
+            ..Default::default()
+        };
+        assert!(run_shortcut("proj1", vec![], &options).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_working_dir() {
+        let config = use_temp_config();
+        let options = AddOptions {
+            working_dir: Some(config.path().to_path_buf()),
+            ..Default::default()
+        };
+        let command = vec!["touch".to_string(), "marker".to_string()];
+        add_shortcut("touch", command.clone(), &options).unwrap();
+        assert!(run_shortcut("touch", vec![], &RunOptions::default())
+            .unwrap()
+            .success());
+        assert!(config.path().join("marker").exists());
+
+        let options = AddOptions {
+            working_dir: Some(config.path().join("missing")),
+            ..Default::default()
+        };
+        assert!(add_shortcut("bad", command, &options).is_err());
+    }
+
+    #[cfg(unix)]
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_log() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("run.log");
+        fs::write(&log, "earlier\n").unwrap();
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo out; echo err >&2".to_string(),
+        ];
+        add_shortcut("proj1", command, &AddOptions::default()).unwrap();
+        let options = RunOptions {
+            log: Some(log.clone()),
+            ..Default::default()
+        };
+        assert!(run_shortcut("proj1", vec![], &options).unwrap().success());
+        let contents = fs::read_to_string(&log).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "earlier");
+        assert!(lines[1..].iter().all(|line| line.starts_with("[20")));
+        assert!(lines.iter().any(|line| line.ends_with("] out")));
+        assert!(lines.iter().any(|line| line.ends_with("] err")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_no_inherit_env() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let script = format!(
+            "echo \"${{HOME:-unset}} ${{PATH:+path}} $GREETING\" > '{}'",
+            out.display()
+        );
+        // The hooks get the same environment as the command
+        let hook = |file: &str| {
+            let script = script.replace(&out.display().to_string(), file);
+            Some(vec!["/bin/sh".to_string(), "-c".to_string(), script])
+        };
+        let (pre, post) = (dir.path().join("pre"), dir.path().join("post"));
+        let options = AddOptions {
+            env: vec![("GREETING".to_string(), "hi".to_string())],
+            pre_run: hook(&pre.display().to_string()),
+            post_run: hook(&post.display().to_string()),
+            ..Default::default()
+        };
+        let command = vec!["/bin/sh".to_string(), "-c".to_string(), script.clone()];
+        add_shortcut("proj1", command, &options).unwrap();
+        let options = RunOptions {
+            no_inherit_env: true,
+            inherit_only: vec!["PATH".to_string()],
+            ..Default::default()
+        };
+        run_shortcut("proj1", vec![], &options).unwrap();
+        assert_eq!(fs::read_to_string(&out).unwrap(), "unset path hi\n");
+        assert_eq!(fs::read_to_string(&pre).unwrap(), "unset path hi\n");
+        assert_eq!(fs::read_to_string(&post).unwrap(), "unset path hi\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_retry() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let counter = dir.path().join("count");
+        // Fails with the number of the attempt on the first two attempts, then succeeds
+        let script = format!(
+            "echo x >> '{0}'; n=$(wc -l < '{0}'); test $n -gt 2 || exit $n",
+            counter.display()
+        );
+        let command = vec!["sh".to_string(), "-c".to_string(), script];
+        add_shortcut("flaky", command, &AddOptions::default()).unwrap();
+        let options = RunOptions {
+            retries: 1,
+            ..Default::default()
+        };
+        let status = run_shortcut("flaky", vec![], &options).unwrap();
+        assert_eq!(status.code(), Some(2));
+        fs::remove_file(&counter).unwrap();
+        let options = RunOptions {
+            retries: 3,
+            ..Default::default()
+        };
+        assert!(run_shortcut("flaky", vec![], &options).unwrap().success());
+        assert_eq!(fs::read_to_string(&counter).unwrap().lines().count(), 3);
+        assert_eq!(load_shortcuts().unwrap()[0].run_count, 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_allocate_pty() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        // Records whether stdout is a terminal, then prints something to relay
+        let script = format!(
+            "if [ -t 1 ]; then echo tty > '{0}'; else echo pipe > '{0}'; fi; echo relayed",
+            out.display()
+        );
+        let command = vec!["sh".to_string(), "-c".to_string(), script];
+        add_shortcut("proj1", command, &AddOptions::default()).unwrap();
+        let options = RunOptions {
+            allocate_pty: true,
+            ..Default::default()
+        };
+        assert!(run_shortcut("proj1", vec![], &options).unwrap().success());
+        assert_eq!(fs::read_to_string(&out).unwrap(), "tty\n");
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(
+            format_elapsed(Duration::from_millis(3142)),
+            "Command finished in 3.142s"
+        );
+        assert_eq!(
+            format_elapsed(Duration::from_micros(2_000_400)),
+            "Command finished in 2.000s"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_repeat_shortcut() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let counter = dir.path().join("count");
+        // Succeeds on the first two runs and fails from the third on
+        let script = format!(
+            "echo x >> '{0}'; test $(wc -l < '{0}') -le 2",
+            counter.display()
+        );
+        let command = vec!["sh".to_string(), "-c".to_string(), script];
+        add_shortcut("flaky", command, &AddOptions::default()).unwrap();
+        let options = RunOptions::default();
+        assert_eq!(
+            repeat_shortcut("flaky", &[], &options, 4, false).unwrap(),
+            (2, 4)
+        );
+        fs::remove_file(&counter).unwrap();
+        assert_eq!(
+            repeat_shortcut("flaky", &[], &options, 4, true).unwrap(),
+            (2, 3)
+        );
+        assert!(repeat_shortcut("missing", &[], &options, 2, false).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_with_follow_up() {
+        let _config = use_temp_config();
+        for (name, code) in [("ok", "0"), ("fails", "1"), ("next", "0"), ("alert", "0")] {
+            let command = vec!["sh".to_string(), "-c".to_string(), format!("exit {}", code)];
+            add_shortcut(name, command, &AddOptions::default()).unwrap();
+        }
+        let options = RunOptions::default();
+        let status = run_with_follow_up("ok", vec![], &options, Some("next"), Some("alert"));
+        assert!(status.unwrap().success());
+        let status = run_with_follow_up("fails", vec![], &options, Some("next"), Some("alert"));
+        assert_eq!(status.unwrap().code(), Some(1));
+        let _ = run_with_follow_up("missing", vec![], &options, None, Some("alert"));
+
+        let runs: Vec<u64> = load_shortcuts()
+            .unwrap()
+            .iter()
+            .map(|s| s.run_count)
+            .collect();
+        assert_eq!(runs, vec![1, 1, 1, 2]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_hooks() {
+        let _config = use_temp_config();
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("log");
+        let append = |word: &str| {
+            vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                format!("echo {} >> '{}'", word, log.display()),
+            ]
+        };
+        let options = AddOptions {
+            pre_run: Some(append("pre")),
+            post_run: Some(append("post")),
+            ..Default::default()
+        };
+        let command = vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()];
+        add_shortcut("proj1", command, &options).unwrap();
+        let status = run_shortcut("proj1", vec![], &RunOptions::default()).unwrap();
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(fs::read_to_string(&log).unwrap(), "pre\npost\n");
+        assert!(run_parallel_shortcuts(&["proj1".to_string()], 2).is_err());
+        assert_eq!(fs::read_to_string(&log).unwrap(), "pre\npost\npre\npost\n");
+
+        let options = UpdateOptions {
+            pre_run: Some(vec!["false".to_string()]),
+            post_run: Some(vec![]),
+            ..Default::default()
+        };
+        update_shortcut("proj1", None, &options).unwrap();
+        assert_eq!(load_shortcuts().unwrap()[0].post_run, None);
+        assert!(run_shortcut("proj1", vec![], &RunOptions::default()).is_err());
+        assert!(run_parallel_shortcuts(&["proj1".to_string()], 2).is_err());
+        assert_eq!(load_shortcuts().unwrap()[0].run_count, 2);
+    }
+
+    #[test]
+    fn test_run_shortcut_env() {
+        let _config = use_temp_config();
+        let options = AddOptions {
+            env: vec![("PROJEXTS_TEST_VAR".to_string(), "yes".to_string())],
+            ..Default::default()
+        };
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "test \"$PROJEXTS_TEST_VAR\" = yes".to_string(),
+        ];
+        add_shortcut("env", command, &options).unwrap();
+        assert!(run_shortcut("env", vec![], &RunOptions::default())
+            .unwrap()
+            .success());
+
+        let options = UpdateOptions {
+            unset_env: vec!["PROJEXTS_TEST_VAR".to_string()],
+            ..Default::default()
+        };
+        update_shortcut("env", None, &options).unwrap();
+        assert!(!run_shortcut("env", vec![], &RunOptions::default())
+            .unwrap()
+            .success());
+        assert!(parse_env_var("=oops").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_all_shortcuts() {
+        let _config = use_temp_config();
+        let tagged = AddOptions {
+            tags: vec!["ci".to_string()],
+            ..Default::default()
+        };
+        let _ = add_shortcut("ok", vec!["true".to_string()], &tagged);
+        let _ = add_shortcut("bad", vec!["false".to_string()], &AddOptions::default());
+        assert!(run_all_shortcuts(&["ci".to_string()], false).is_ok());
+        assert!(run_all_shortcuts(&[], false).is_err());
+        assert!(run_all_shortcuts(&[], true).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_parallel_shortcuts() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("ok", vec!["true".to_string()], &AddOptions::default());
+        let _ = add_shortcut("bad", vec!["false".to_string()], &AddOptions::default());
+        let names = ["ok".to_string(), "ok".to_string(), "bad".to_string()];
+        assert!(run_parallel_shortcuts(&names[..2], 1).is_ok());
+        assert!(run_parallel_shortcuts(&names, 2).is_err());
+        let result = run_parallel_shortcuts(&["missing".to_string()], 1);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_run_shortcut_dry_run() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("fails", vec!["false".to_string()], &AddOptions::default());
+        let options = RunOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let status = run_shortcut("fails", vec!["--flag".to_string()], &options).unwrap();
+        assert!(status.success());
+
+        // Unfilled placeholders don't make a dry run ask for them
+        let command = vec![
+            "git".to_string(),
+            "checkout".to_string(),
+            "{BRANCH}".to_string(),
+        ];
+        add_shortcut("checkout", command, &AddOptions::default()).unwrap();
+        assert!(run_shortcut("checkout", vec![], &options).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_shortcut_timeout() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "slow",
+            vec!["sleep".to_string(), "10".to_string()],
+            &AddOptions::default(),
+        );
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let result = run_shortcut("slow", vec![], &options);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_update_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "proj1",
+            vec!["echo".to_string(), "Hello".to_string()],
+            &AddOptions::default(),
+        );
+        let result = update_shortcut(
+            "proj1",
+            Some(vec!["echo".to_string(), "World".to_string()]),
+            &UpdateOptions::default(),
+        );
+        assert!(result.is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(
+            shortcuts[0].run_command,
+            vec!["echo".to_string(), "World".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_update_arguments() {
+        let _config = use_temp_config();
+        let command = vec!["echo".to_string(), "a".to_string(), "b".to_string()];
+        let _ = add_shortcut("proj1", command, &AddOptions::default());
+        let options = UpdateOptions {
+            add_arg: Some("--verbose".to_string()),
+            remove_arg: Some("a".to_string()),
+            insert_arg_at: Some((0, "first".to_string())),
+            ..Default::default()
+        };
+        update_shortcut("proj1", None, &options).unwrap();
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(
+            shortcuts[0].run_command,
+            vec!["echo", "first", "b", "--verbose"]
+        );
+
+        let options = UpdateOptions {
+            remove_arg: Some("echo".to_string()),
+            ..Default::default()
+        };
+        let err = update_shortcut("proj1", None, &options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let options = UpdateOptions {
+            insert_arg_at: Some((4, "late".to_string())),
+            ..Default::default()
+        };
+        assert!(update_shortcut("proj1", None, &options).is_err());
+        assert_eq!(parse_insert_arg("2=--x"), Ok((2, "--x".to_string())));
+        assert!(parse_insert_arg("x=1").is_err());
+    }
+
+    #[test]
+    fn test_update_set_name() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default());
+        let _ = add_shortcut("proj2", vec!["echo".to_string()], &AddOptions::default());
+        let options = UpdateOptions {
+            new_name: Some("renamed".to_string()),
+            add_arg: Some("hi".to_string()),
+            ..Default::default()
+        };
+        update_shortcut("proj1", None, &options).unwrap();
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[0].project_name, "renamed");
+        assert_eq!(shortcuts[0].run_command, vec!["echo", "hi"]);
+
+        let options = UpdateOptions {
+            new_name: Some("proj2".to_string()),
+            add_arg: Some("again".to_string()),
+            ..Default::default()
+        };
+        let err = update_shortcut("renamed", None, &options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(load_shortcuts().unwrap()[0].run_command, vec!["echo", "hi"]);
+    }
+
+    #[test]
+    fn test_shortcut_tags() {
+        let _config = use_temp_config();
+        let options = AddOptions {
+            tags: vec!["rust".to_string(), "work".to_string()],
+            ..Default::default()
+        };
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &options);
+        let options = UpdateOptions {
+            add_tags: vec!["cli".to_string(), "rust".to_string()],
+            remove_tags: vec!["work".to_string()],
+            ..Default::default()
+        };
+        let result = update_shortcut("proj1", None, &options);
+        assert!(result.is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(
+            shortcuts[0].tags,
+            vec!["rust".to_string(), "cli".to_string()]
+        );
+        let options = ListOptions {
+            filter_tags: vec!["cli".to_string()],
+            ..Default::default()
+        };
+        assert!(list_shortcuts(&options).is_ok());
+    }
+
+    #[test]
+    fn test_shortcut_description() {
+        let _config = use_temp_config();
+        let options = AddOptions {
+            description: Some("Prints a greeting".to_string()),
+            ..Default::default()
+        };
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &options);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(
+            shortcuts[0].description.as_deref(),
+            Some("Prints a greeting")
+        );
+
+        let options = UpdateOptions {
+            description: Some(String::new()),
+            ..Default::default()
+        };
+        let _ = update_shortcut("proj1", None, &options);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[0].description, None);
+        assert_eq!(truncate("abcdefgh", 6), "abc...");
+        assert_eq!(truncate("abc", 6), "abc");
+        assert_eq!(truncate("日本語のテキスト", 9), "日本語...");
+        assert_eq!(truncate("日本語", 6), "日本語");
+    }
+
+    #[test]
+    fn test_load_shortcuts_without_tags() {
+        let _config = use_temp_config();
+        fs::write(
+            config_file_path(),
+            r#"[{"project_name": "proj1", "run_command": ["echo"]}]"#,
+        )
+        .unwrap();
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[0].project_name, "proj1");
+        assert!(shortcuts[0].tags.is_empty());
+    }
+
+    #[test]
+    fn test_run_doctor() {
+        let _config = use_temp_config();
+        let results = run_doctor();
+        assert_eq!(results[0].check, "Config file");
+        assert_eq!(results[0].status, DiagnosticStatus::Warn);
+        assert_eq!(results[1].status, DiagnosticStatus::Ok);
+
+        fs::write(config_file_path(), "not json").unwrap();
+        assert_eq!(run_doctor()[0].status, DiagnosticStatus::Error);
+
+        let shortcuts = vec![
+            Shortcut {
+                project_name: "a".to_string(),
+                ..Default::default()
+            },
+            Shortcut {
+                project_name: "A".to_string(),
+                run_command: vec!["ls".to_string()],
+                ..Default::default()
+            },
+        ];
+        let results = diagnose_shortcuts(&shortcuts);
+        assert_eq!(
+            results,
+            vec![
+                DiagnosticResult::new("Empty commands", DiagnosticStatus::Error, "a"),
+                DiagnosticResult::new("Duplicate names", DiagnosticStatus::Error, "A"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_show_config() {
+        let _config = use_temp_config();
+        let mut output = Vec::new();
+        let err = show_config(&mut output).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        let raw = "[{\"project_name\":\"a\",\"run_command\":[\"ls\"]}]";
+        fs::write(config_file_path(), raw).unwrap();
+        show_config(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_diff_shortcut_lists() {
+        let shortcut = |name: &str, command: &str| Shortcut {
+            project_name: name.to_string(),
+            run_command: vec![command.to_string()],
+            ..Default::default()
+        };
+        let a = vec![
+            shortcut("same", "ls"),
+            shortcut("gone", "pwd"),
+            shortcut("changed", "make"),
+        ];
+        let mut b = vec![
+            shortcut("new", "date"),
+            shortcut("changed", "cargo"),
+            shortcut("same", "ls"),
+        ];
+        // Statistics don't count as a modification
+        b[2].run_count = 5;
+        let diff = diff_shortcut_lists(&a, &b);
+        assert_eq!(diff.added, vec![&b[0]]);
+        assert_eq!(diff.removed, vec![&a[1]]);
+        assert_eq!(diff.modified, vec![(&a[2], &b[1])]);
+        assert!(diff_shortcut_lists(&a, &a).is_empty());
+    }
+
+    /// A TOML export as written by `export-toml`, with one shortcut.
+    const TOML_FIXTURE: &str = r#"version = 1
+
+[[shortcuts]]
+project_name = "build"
+run_command = ["cargo", "build"]
+tags = ["rust"]
+description = "Build the project"
+run_count = 2
+resolve = "eager"
+
+[shortcuts.env]
+RUST_LOG = "debug"
+"#;
+
+    #[test]
+    fn test_export_as_toml() {
+        let config = use_temp_config();
+        let mut env = HashMap::new();
+        env.insert("RUST_LOG".to_string(), "debug".to_string());
+        save_shortcuts(&[Shortcut {
+            project_name: "build".to_string(),
+            run_command: vec!["cargo".to_string(), "build".to_string()],
+            tags: vec!["rust".to_string()],
+            description: Some("Build the project".to_string()),
+            env,
+            run_count: 2,
+            ..Default::default()
+        }])
+        .unwrap();
+        let path = config.path().join("export.toml");
+        assert_eq!(export_as_toml(&path).unwrap(), 1);
+        assert_eq!(
+            parse_toml_config(&fs::read_to_string(&path).unwrap()).unwrap(),
+            parse_toml_config(TOML_FIXTURE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_import_from_toml() {
+        let config = use_temp_config();
+        let path = config.path().join("import.toml");
+        fs::write(&path, TOML_FIXTURE).unwrap();
+        let _ = add_shortcut("other", vec!["ls".to_string()], &AddOptions::default());
+        assert_eq!(import_from_toml(&path, true).unwrap(), 1);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[1].run_command, vec!["cargo", "build"]);
+        assert_eq!(shortcuts[1].env["RUST_LOG"], "debug");
+        assert_eq!(import_from_toml(&path, false).unwrap(), 1);
+        assert_eq!(load_shortcuts().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_toml_config() {
+        let config = use_temp_config();
+        let path = config.path().join("config.toml");
+        set_config_override(Some(path.clone()));
+        assert_eq!(config_format(), ConfigFormat::Toml);
+        assert!(load_shortcuts().unwrap().is_empty());
+
+        let options = AddOptions {
+            env: vec![("KEY".to_string(), "value".to_string())],
+            description: Some("Say hi".to_string()),
+            ..Default::default()
+        };
+        add_shortcut(
+            "hello",
+            vec!["echo".to_string(), "hi".to_string()],
+            &options,
+        )
+        .unwrap();
+        let data = fs::read_to_string(&path).unwrap();
+        assert!(data.contains("[[shortcuts]]"));
+        assert!(data.contains("project_name = \"hello\""));
+
+        let shortcuts = load_shortcuts_from_toml(&path).unwrap();
+        assert_eq!(shortcuts, load_shortcuts().unwrap());
+        assert_eq!(
+            shortcuts[0].env.get("KEY").map(String::as_str),
+            Some("value")
+        );
+        assert!(parse_toml_config("version = 99").is_err());
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("config.json")),
+            ConfigFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_hook_flags() {
+        let cli = Cli::try_parse_from([
+            "projexts",
+            "add",
+            "web",
+            "--pre-run",
+            "docker compose up -d",
+            "--",
+            "npm",
+            "start",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Add { pre_run, .. } => assert_eq!(
+                pre_run,
+                Some(vec![
+                    "docker".to_string(),
+                    "compose".to_string(),
+                    "up".to_string(),
+                    "-d".to_string()
+                ])
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_reset_requires_confirm() {
+        let parsed = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+            Commands::Reset { confirm } => confirm,
+            _ => unreachable!(),
+        };
+        assert!(!parsed(&["projexts", "reset"]));
+        assert!(parsed(&["projexts", "reset", "--confirm"]));
+        assert!(parsed(&["projexts", "reset", "--force"]));
+    }
+
+    #[test]
+    fn test_reset_and_restore() {
+        let _config = use_temp_config();
+        assert_eq!(
+            restore_shortcuts().unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default());
+        reset_shortcuts().unwrap();
+        assert!(!config_file_path().exists());
+        restore_shortcuts().unwrap();
+        assert_eq!(list_names().unwrap(), vec!["proj1"]);
+
+        // A configuration created after the reset is backed up before the restore replaces it
+        reset_shortcuts().unwrap();
+        let _ = add_shortcut("proj2", vec!["echo".to_string()], &AddOptions::default());
+        restore_shortcuts().unwrap();
+        assert_eq!(list_names().unwrap(), vec!["proj1"]);
+        if std::env::var_os("PROJEXTS_MAX_BACKUPS").is_none() {
+            let mut backups: Vec<PathBuf> = fs::read_dir(backup_dir())
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect();
+            backups.sort();
+            let newest = fs::read_to_string(backups.last().unwrap()).unwrap();
+            assert!(newest.contains("proj2"));
+        }
+    }
+
+    #[test]
+    fn test_backup_config() {
+        let _config = use_temp_config();
+        for i in 0..DEFAULT_MAX_BACKUPS + 3 {
+            let name = format!("proj{}", i);
+            let _ = add_shortcut(&name, vec!["echo".to_string()], &AddOptions::default());
+        }
+        if std::env::var_os("PROJEXTS_MAX_BACKUPS").is_none() {
+            let backups = || fs::read_dir(backup_dir()).unwrap().count();
+            assert_eq!(backups(), DEFAULT_MAX_BACKUPS);
+
+            // Recording runs keeps the backups of the user's changes
+            let newest = || {
+                let mut names: Vec<PathBuf> = fs::read_dir(backup_dir())
+                    .unwrap()
+                    .map(|entry| entry.unwrap().path())
+                    .collect();
+                names.sort();
+                names.pop()
+            };
+            let before = newest();
+            for _ in 0..DEFAULT_MAX_BACKUPS + 1 {
+                record_run("proj0").unwrap();
+            }
+            assert_eq!(newest(), before);
+            assert_eq!(backups(), DEFAULT_MAX_BACKUPS);
+        }
+
+        assert!(is_backup_of(
+            "config-20240101T000000.000000Z.json",
+            "config",
+            "json"
+        ));
+        assert!(!is_backup_of(
+            "config-2-20240101T000000.000000Z.json",
+            "config",
+            "json"
+        ));
+        assert!(!is_backup_of(
+            "config-20240101T000000.000000Z.toml",
+            "config",
+            "json"
+        ));
+        assert!(!is_backup_of("config.json", "config", "json"));
+    }
 
-            // Change to the directory
-            std::env::set_current_dir(dir)?;
+    #[test]
+    fn test_profile_backups() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("projexts");
+        let default_config = dir.join("config.json");
+        let profile_config = dir.join("profiles").join("config.json");
+        let _config = use_temp_config();
+        let add = |name: &str| {
+            add_shortcut(name, vec!["echo".to_string()], &AddOptions::default()).unwrap()
+        };
+        let default_backups = || {
+            fs::read_dir(dir.join("backups"))
+                .unwrap()
+                .filter(|entry| entry.as_ref().unwrap().path().is_file())
+                .count()
+        };
 
-            // Add changes
-            Command::new("git").arg("add").arg(".").status()?;
+        set_config_override(Some(default_config));
+        add("proj1");
+        add("proj2");
+        assert_eq!(backup_dir(), dir.join("backups"));
+        let before = default_backups();
+        set_config_override(Some(profile_config));
+        for i in 0..DEFAULT_MAX_BACKUPS + 2 {
+            add(&format!("proj{}", i));
+        }
+        assert_eq!(backup_dir(), dir.join("backups").join("profiles"));
 
-            // Commit changes
-            Command::new("git")
-                .arg("commit")
-                .arg("-m")
-                .arg(commit_message)
-                .status()?;
+        assert_eq!(default_backups(), before);
+    }
 
-            // Push changes
-            Command::new("git").arg("push").status()?;
+    #[test]
+    fn test_lock_config() {
+        let _config = use_temp_config();
+        let lock = lock_config().unwrap();
+        // Nested locks on the same thread don't wait for the outer one
+        assert!(add_shortcut("proj1", vec!["echo".to_string()], &AddOptions::default()).is_ok());
 
-            println!("Changes committed and pushed from directory {:?}", dir);
-        } else {
-            eprintln!("Error: Run command is empty for shortcut '{}'", name);
-        }
-    } else {
-        eprintln!("Error: No shortcut found with name '{}'", name);
+        // Another handle on the lock file conflicts while the lock is held
+        let other = fs::File::open(config_sibling_path(".lock")).unwrap();
+        assert!(fs2::FileExt::try_lock_exclusive(&other).is_err());
+        drop(lock);
+        assert!(fs2::FileExt::try_lock_exclusive(&other).is_ok());
     }
-    Ok(())
-}
 
-/// Represents a shortcut for a project, including the project's name and the command to run.
-///
-/// This struct is used to store and manage shortcuts for projects, where each shortcut has:
-/// - `project_name`: The name of the project associated with the shortcut.
-/// - `run_command`: A vector of strings representing the command and its arguments to execute the project.
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-struct Shortcut {
-    /// The name of the project associated with the shortcut.
-    project_name: String,
+    #[test]
+    fn test_parse_config() {
+        let shortcuts = vec![Shortcut {
+            project_name: "proj1".to_string(),
+            ..Default::default()
+        }];
+        let data = serialize_config(&shortcuts).unwrap();
+        assert!(data.contains("\"version\": 1"));
+        assert_eq!(parse_config(&data).unwrap(), shortcuts);
 
-    /// The command (with its arguments) to run the project.
-    run_command: Vec<String>,
-}
+        let newer = r#"{"version": 99, "shortcuts": {}}"#;
+        let result = parse_config(newer);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert!(parse_config("not json").is_err());
 
-/// A command-line interface (CLI) tool to manage project shortcuts.
-///
-/// This struct represents the root of the CLI and serves as an entry point for handling
-/// various commands that interact with project shortcuts (e.g., adding, removing, listing shortcuts).
-///
-/// The CLI tool uses `clap` to parse commands and subcommands, providing a user-friendly way to interact
-/// with the project management functionality.
-#[derive(Parser)]
-#[command(name = "projexts", about = "A CLI tool to manage project shortcuts")]
-struct Cli {
-    /// The subcommand to execute.
-    ///
-    /// This field allows the user to specify which action to take. Each subcommand corresponds to a
-    /// specific operation on the project shortcuts (e.g., adding, removing, listing shortcuts).
-    #[command(subcommand)]
-    command: Commands,
-}
+        // A missing version is read as version 0
+        let unversioned = r#"{"shortcuts": [{"project_name": "proj1", "run_command": []}]}"#;
+        assert_eq!(parse_config(unversioned).unwrap(), shortcuts);
+        let err = parse_config(r#"{"name": "proj1"}"#).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("shortcuts"));
+    }
 
-/// Commands for managing project shortcuts.
-#[derive(Subcommand)]
-enum Commands {
-    /// Add a new shortcut
-    Add {
-        /// Name of the project
-        name: String,
-        /// Command to run the project (supports spaces and arguments)
-        #[arg(last = true)]
-        command: Vec<String>,
-    },
-    /// Removes a shortcut
-    Remove {
-        /// Name of the project
-        name: String,
-    },
-    /// List all shortcuts
-    List,
-    /// Opens the enclosed folder of the run command
-    Open { name: String },
-    /// Open a file from a shortcut
-    OpenFile {
-        /// Name of the project
-        name: String,
-    },
-    /// Run a shortcut by name
-    Run {
-        /// Name of the project to run
-        name: String,
-        /// Additional arguments to pass to the command
-        #[arg(last = true)]
-        extra_args: Vec<String>,
-    },
-    /// Update an existing shortcut
-    Update {
-        /// Name of the project
-        name: String,
-        /// Command to run the project (supports spaces and arguments)
-        #[arg(last = true)]
-        command: Vec<String>,
-    },
-    /// Add, commit, and push changes to git in directory of the shortcut
-    GitPush {
-        /// Name of the project
-        name: String,
-        /// Commit message
-        commit_message: String,
-    },
-    /// Removes all saved shortcuts
-    Reset,
-}
+    #[test]
+    fn test_rename_shortcut() {
+        let _config = use_temp_config();
+        let options = AddOptions {
+            tags: vec!["rust".to_string()],
+            ..Default::default()
+        };
+        let _ = add_shortcut("proj1", vec!["echo".to_string()], &options);
+        let _ = add_shortcut("proj2", vec!["ls".to_string()], &AddOptions::default());
 
-/// The main entry point for the `projexts` CLI tool.
-///
-/// This function parses the command-line arguments using `Cli::parse()` and dispatches the appropriate
-/// subcommand based on the user's input. Each subcommand corresponds to a specific operation (such as adding,
-/// removing, or listing shortcuts), and the function handles any errors that occur during execution.
-///
-/// It performs the following tasks:
-/// - Adds a new shortcut using the `add_shortcut` function.
-/// - Removes a shortcut using the `remove_shortcut` function.
-/// - Lists all shortcuts using the `list_shortcuts` function.
-/// - Opens the project folder using the `open_project_folder` function.
-/// - Opens a file from a shortcut using the `open_file_from_shortcut` function.
-/// - Runs a shortcut's command using the `run_shortcut` function.
-/// - Updates an existing shortcut using the `update_shortcut` function.
-/// - Pushes changes to Git using the `git_push` function.
-fn main() {
-    let args = Cli::parse();
+        assert!(rename_shortcut("proj1", "renamed", false).is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[0].project_name, "renamed");
+        assert_eq!(shortcuts[0].tags, vec!["rust".to_string()]);
+        assert!(shortcuts[0].created_at.is_some());
+        assert!(shortcuts[0].updated_at.is_some());
+        assert!(shortcuts[1].updated_at.is_none());
 
-    match args.command {
-        Commands::Add { name, command } => {
-            println!("Adding shortcut: {} -> {:?}", name, command);
-            if let Err(e) = add_shortcut(&name, command) {
-                eprintln!("Failed to add shortcut: {}", e);
-            }
-        }
-        Commands::Remove { name } => {
-            println!("Removing shortcut: {}", name);
-            if let Err(e) = remove_shortcut(&name) {
-                eprintln!("Failed to remove shortcut: {}", e);
-            }
-        }
-        Commands::List => {
-            if let Err(e) = list_shortcuts() {
-                eprintln!("Failed to list shortcuts: {}", e);
-            }
-        }
-        Commands::Open { name } => {
-            if let Err(e) = open_project_folder(&name) {
-                eprintln!("Failed to open project folder: {}", e);
-            }
-        }
-        Commands::OpenFile { name } => {
-            if let Err(e) = open_file_from_shortcut(&name) {
-                eprintln!("Failed to open file from shortcut: {}", e);
-            }
-        }
-        Commands::Run { name, extra_args } => {
-            println!(
-                "Running shortcut '{}' with extra arguments: {:?}",
-                name, extra_args
-            );
-            if let Err(e) = run_shortcut(&name, extra_args) {
-                eprintln!("Failed to run shortcut: {}", e);
-            }
-        }
-        Commands::Update { name, command } => {
-            println!("Updating shortcut: {} -> {:?}", name, command);
-            if let Err(e) = update_shortcut(&name, Some(command)) {
-                eprintln!("Failed to update shortcut: {}", e);
-            }
-        }
-        Commands::GitPush {
-            name,
-            commit_message,
-        } => {
-            println!("Pushing changes with commit message: {}", commit_message);
-            if let Err(e) = git_push(&name, &commit_message) {
-                eprintln!("Failed to push changes: {}", e);
-            }
-        }
-        Commands::Reset => {
-            if let Err(e) = reset_shortcuts() {
-                eprintln!("Failed to reset shortcuts: {}", e);
-            }
-        }
+        let result = rename_shortcut("missing", "other", false);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+        let result = rename_shortcut("renamed", "proj2", false);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert!(rename_shortcut("renamed", "Renamed", false).is_ok());
     }
-}
 
-// Testing Code
-/////////////////////////////////////////////////////////////////////////////////
+    #[test]
+    fn test_duplicate_shortcut() {
+        let _config = use_temp_config();
+        let options = AddOptions {
+            description: Some("Release build".to_string()),
+            ..Default::default()
+        };
+        let _ = add_shortcut("release", vec!["cargo".to_string()], &options);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert!(duplicate_shortcut("release", "debug").is_ok());
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[1].project_name, "debug");
+        assert_eq!(shortcuts[1].run_command, shortcuts[0].run_command);
+        assert_eq!(shortcuts[1].description, shortcuts[0].description);
 
-    #[test]
-    fn test_config_file_path() {
-        let path = config_file_path();
-        let expected_path = dirs::home_dir().unwrap().join(".projexts_config.json");
-        assert_eq!(path, expected_path);
-        _ = reset_shortcuts();
+        let result = duplicate_shortcut("missing", "other");
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+        let result = duplicate_shortcut("release", "debug");
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
     }
 
     #[test]
-    fn test_load_shortcuts() {
-        let _ = reset_shortcuts();
-        let result = load_shortcuts();
-        assert!(result.is_ok());
-        let shortcuts = result.unwrap();
-        assert!(shortcuts.is_empty());
+    fn test_locked_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut("proj1", vec!["ls".to_string()], &AddOptions::default());
+        lock_shortcut("proj1").unwrap();
+
+        let err = remove_shortcut("proj1", false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        let options = UpdateOptions {
+            add_tags: vec!["tag".to_string()],
+            ..Default::default()
+        };
+        let err = update_shortcut("proj1", None, &options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        let err = rename_shortcut("proj1", "other", false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(load_shortcuts().unwrap()[0].tags.is_empty());
+
+        let forced = UpdateOptions {
+            allow_locked: true,
+            ..options.clone()
+        };
+        update_shortcut("proj1", None, &forced).unwrap();
+        assert_eq!(load_shortcuts().unwrap()[0].tags, vec!["tag"]);
+        rename_shortcut("proj1", "other", true).unwrap();
+        rename_shortcut("other", "proj1", true).unwrap();
+        assert!(load_shortcuts().unwrap()[0].locked);
+        unlock_shortcut("proj1").unwrap();
+        assert!(!load_shortcuts().unwrap()[0].locked);
+        update_shortcut("proj1", None, &options).unwrap();
+        lock_shortcut("proj1").unwrap();
+        remove_shortcut("proj1", true).unwrap();
+        assert!(load_shortcuts().unwrap().is_empty());
+        assert!(unlock_shortcut("proj1").is_err());
+
+        add_shortcut("proj1", vec!["ls".to_string()], &AddOptions::default()).unwrap();
+        lock_shortcut("proj1").unwrap();
+        let force = AddOptions {
+            force: true,
+            ..Default::default()
+        };
+        let err = add_shortcut("proj1", vec!["pwd".to_string()], &force).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        let imported = vec![Shortcut {
+            project_name: "proj1".to_string(),
+            run_command: vec!["pwd".to_string()],
+            ..Default::default()
+        }];
+        assert_eq!(store_imported_shortcuts(imported, false).unwrap(), 0);
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts.len(), 1);
+        assert!(shortcuts[0].locked);
+        assert_eq!(shortcuts[0].run_command, vec!["ls"]);
     }
 
     #[test]
-    fn test_save_shortcuts() {
-        let shortcuts = vec![
-            Shortcut {
-                project_name: "proj1".to_string(),
-                run_command: vec!["echo".to_string(), "Hello".to_string()],
-            },
-            Shortcut {
-                project_name: "proj2".to_string(),
-                run_command: vec!["echo".to_string(), "World".to_string()],
+    fn test_add_copy_from() {
+        let _config = use_temp_config();
+        let options = AddOptions {
+            description: Some("Release build".to_string()),
+            tags: vec!["rust".to_string()],
+            ..Default::default()
+        };
+        let _ = add_shortcut("release", vec!["cargo".to_string()], &options);
+
+        let copy = AddOptions {
+            copy_from: Some("release".to_string()),
+            tags: vec!["debug".to_string()],
+            ..Default::default()
+        };
+        add_shortcut("debug", Vec::new(), &copy).unwrap();
+        let shortcuts = load_shortcuts().unwrap();
+        assert_eq!(shortcuts[1].run_command, vec!["cargo"]);
+        assert_eq!(shortcuts[1].description.as_deref(), Some("Release build"));
+        assert_eq!(shortcuts[1].tags, vec!["debug"]);
+
+        let result = add_shortcut(
+            "other",
+            Vec::new(),
+            &AddOptions {
+                copy_from: Some("missing".to_string()),
+                ..Default::default()
             },
-        ];
-        let result = save_shortcuts(&shortcuts);
+        );
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_open_file_from_shortcut() {
+        let _config = use_temp_config();
+        let _ = add_shortcut(
+            "proj1",
+            vec!["Cargo.toml".to_string()],
+            &AddOptions::default(),
+        );
+        let result = open_file_from_shortcut("proj1", None);
         assert!(result.is_ok());
-        let loaded_shortcuts = load_shortcuts().unwrap();
-        assert_eq!(shortcuts, loaded_shortcuts);
+        let result = open_file_from_shortcut("proj1", Some("true"));
+        assert!(result.is_ok());
+        let err = open_file_from_shortcut("missing", None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    /// Runs `git` with `args` in `dir` and checks that it succeeded.
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .stdout(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    /// Creates a repository with a shortcut named `name` in the temporary config directory, with a
+    /// Git identity configured so commits work without a global one.
+    fn init_test_repo(config: &tempfile::TempDir, name: &str) -> PathBuf {
+        let path = config.path().join(name);
+        git_init_and_add(name, &path, None).unwrap();
+        run_git(&path, &["config", "user.name", "test"]);
+        run_git(&path, &["config", "user.email", "test@example.com"]);
+        path
     }
 
     #[test]
-    fn test_add_shortcut() {
-        let _ = reset_shortcuts();
-        let result = add_shortcut("proj1", vec!["echo".to_string(), "Hello".to_string()]);
+    fn test_git_push() {
+        let config = use_temp_config();
+        let remote = config.path().join("remote.git");
+        run_git(
+            config.path(),
+            &["init", "-q", "--bare", remote.to_str().unwrap()],
+        );
+        let path = init_test_repo(&config, "proj1");
+        run_git(
+            &path,
+            &["remote", "add", "origin", remote.to_str().unwrap()],
+        );
+        fs::write(path.join("README.md"), "hello\n").unwrap();
+        let result = git_push("proj1", "Initial commit", &PushOptions::default());
         assert!(result.is_ok());
-        let shortcuts = load_shortcuts().unwrap();
-        if shortcuts.len() != 1 {
-            panic!(
-                "Expected 1 shortcut, found {}: {:?}",
-                shortcuts.len(),
-                shortcuts
-            );
-        }
-        assert_eq!(shortcuts[0].project_name, "proj1");
-        assert_eq!(
-            shortcuts[0].run_command,
-            vec!["echo".to_string(), "Hello".to_string()]
+
+        // The push fails without a remote, even though the commit succeeds
+        run_git(&path, &["remote", "remove", "origin"]);
+        fs::write(path.join("README.md"), "changed\n").unwrap();
+        let result = git_push("proj1", "Change README", &PushOptions::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_git_push_failed_amend() {
+        let config = use_temp_config();
+        let remote = config.path().join("remote.git");
+        run_git(
+            config.path(),
+            &["init", "-q", "--bare", remote.to_str().unwrap()],
+        );
+        let path = init_test_repo(&config, "proj1");
+        run_git(
+            &path,
+            &["remote", "add", "origin", remote.to_str().unwrap()],
         );
+        fs::write(path.join("README.md"), "hello\n").unwrap();
+
+        // There is no commit to amend yet, so nothing may be pushed
+        let options = PushOptions {
+            amend: true,
+            ..Default::default()
+        };
+        assert!(git_push("proj1", "Amended", &options).is_err());
+        let output = Command::new("git")
+            .args(["ls-remote", "--heads", remote.to_str().unwrap()])
+            .output()
+            .unwrap();
+        assert!(output.stdout.is_empty());
     }
 
     #[test]
-    fn test_remove_shortcut() {
-        let _ = reset_shortcuts();
-        let _ = add_shortcut("proj1", vec!["echo".to_string(), "Hello".to_string()]);
-        let result = remove_shortcut("proj1");
-        assert!(result.is_ok());
+    fn test_git_commit_failure() {
+        let config = use_temp_config();
+        let path = init_test_repo(&config, "proj1");
+        // Nothing to commit
+        assert!(git_commit("proj1", "Empty", false, false).is_err());
+        fs::write(path.join("README.md"), "hello\n").unwrap();
+        assert!(git_commit("proj1", "Add README", false, false).is_ok());
+    }
+
+    #[test]
+    fn test_git_init_and_add() {
+        let config = use_temp_config();
+        let path = config.path().join("new-project");
+        git_init_and_add("new", &path, Some("main")).unwrap();
+        let head = fs::read_to_string(path.join(".git/HEAD")).unwrap();
+        assert_eq!(head.trim(), "ref: refs/heads/main");
         let shortcuts = load_shortcuts().unwrap();
-        assert!(shortcuts.is_empty());
+        assert_eq!(shortcuts[0].project_name, "new");
+        assert_eq!(
+            find_project_dir("new").unwrap(),
+            fs::canonicalize(&path).unwrap()
+        );
+
+        let error = git_init_and_add("other", &path, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        let error = git_init_and_add("new", &config.path().join("again"), None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert!(!config.path().join("again").exists());
     }
 
     #[test]
-    fn test_list_shortcuts() {
-        let _ = reset_shortcuts();
-        let _ = add_shortcut("proj1", vec!["echo".to_string(), "Hello".to_string()]);
-        let _ = add_shortcut("proj2", vec!["echo".to_string(), "World".to_string()]);
-        let result = list_shortcuts();
-        assert!(result.is_ok());
+    fn test_git_clone_and_add() {
+        assert_eq!(
+            repo_name_from_url("https://github.com/user/projexts.git").as_deref(),
+            Some("projexts")
+        );
+        assert_eq!(
+            repo_name_from_url("git@github.com:user/projexts.git/").as_deref(),
+            Some("projexts")
+        );
+        assert_eq!(
+            repo_name_from_url("https://host/"),
+            Some("host".to_string())
+        );
+        assert_eq!(repo_name_from_url(""), None);
+
+        let config = use_temp_config();
+        let origin = config.path().join("origin");
+        git_init_and_add("origin", &origin, None).unwrap();
+        let dest = config.path().join("clone");
+        git_clone_and_add(origin.to_str().unwrap(), "clone", &dest).unwrap();
+        assert!(dest.join(".git").is_dir());
+        assert_eq!(
+            find_project_dir("clone").unwrap(),
+            fs::canonicalize(&dest).unwrap()
+        );
+
+        let error = git_clone_and_add(origin.to_str().unwrap(), "again", &dest).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
     }
 
     #[test]
-    fn test_open_project_folder() {
-        let _ = reset_shortcuts();
-        let _ = add_shortcut("proj1", vec![".".to_string()]);
-        let result = open_project_folder("proj1");
-        assert!(result.is_ok());
+    fn test_git_pull_and_push_all() {
+        let config = use_temp_config();
+        let origin = config.path().join("origin");
+        git_init_and_add("origin", &origin, None).unwrap();
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(["commit", "--allow-empty", "-q", "-m", "first"])
+            .current_dir(&origin)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        git_clone_and_add(
+            origin.to_str().unwrap(),
+            "clone",
+            &config.path().join("clone"),
+        )
+        .unwrap();
+        let plain = config.path().join("plain");
+        fs::create_dir(&plain).unwrap();
+        add_shortcut(
+            "plain",
+            vec![plain.to_string_lossy().to_string()],
+            &AddOptions::default(),
+        )
+        .unwrap();
+
+        // The clone pulls from origin, which has no remote to pull from itself
+        assert_eq!(git_pull_all(false).unwrap(), (1, 1));
+        assert_eq!(git_push_all(&PushOptions::default()).unwrap(), (1, 1));
+        assert!(Cli::try_parse_from(["projexts", "git-pull", "clone", "--all"]).is_err());
     }
 
     #[test]
-    fn test_run_shortcut() {
-        let _ = reset_shortcuts();
-        let _ = add_shortcut("proj1", vec!["echo".to_string(), "Hello".to_string()]);
-        let result = run_shortcut("proj1", vec![]);
-        assert!(result.is_ok());
+    fn test_git_diff() {
+        let config = use_temp_config();
+        let path = config.path().join("repo");
+        git_init_and_add("repo", &path, None).unwrap();
+        fs::write(path.join("file.txt"), "one\n").unwrap();
+        let status = Command::new("git")
+            .args(["add", "file.txt"])
+            .current_dir(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert!(git_diff("repo", false).is_ok());
+        assert!(git_diff("repo", true).is_ok());
     }
 
     #[test]
-    fn test_update_shortcut() {
-        let _ = reset_shortcuts();
-        let _ = add_shortcut("proj1", vec!["echo".to_string(), "Hello".to_string()]);
-        let result = update_shortcut("proj1", Some(vec!["echo".to_string(), "World".to_string()]));
-        assert!(result.is_ok());
-        let shortcuts = load_shortcuts().unwrap();
+    fn test_resolve_project_dir() {
+        let mut shortcut = Shortcut {
+            run_command: vec!["/usr/bin/env".to_string()],
+            ..Default::default()
+        };
         assert_eq!(
-            shortcuts[0].run_command,
-            vec!["echo".to_string(), "World".to_string()]
+            resolve_project_dir(&shortcut).unwrap(),
+            PathBuf::from("/usr/bin")
+        );
+        shortcut.working_dir = Some(PathBuf::from("/srv"));
+        assert_eq!(
+            resolve_project_dir(&shortcut).unwrap(),
+            PathBuf::from("/srv")
         );
+        assert!(resolve_project_dir(&Shortcut::default()).is_err());
     }
 
     #[test]
-    fn test_open_file_from_shortcut() {
-        let _ = reset_shortcuts();
-        let _ = add_shortcut("proj1", vec!["Cargo.toml".to_string()]);
-        let result = open_file_from_shortcut("proj1");
-        assert!(result.is_ok());
+    fn test_git_outside_repository() {
+        let config = use_temp_config();
+        let dir = config.path().to_string_lossy().to_string();
+        let _ = add_shortcut("proj1", vec![dir], &AddOptions::default());
+        assert!(git_pull("proj1", false).is_err());
+        assert!(git_diff("proj1", false).is_err());
+        assert!(git_status("proj1").is_err());
+        assert!(git_branch("proj1", true).is_err());
+        assert!(git_log("proj1", 10).is_err());
+        assert!(git_stash("proj1").is_err());
+        assert!(git_stash_pop("proj1").is_err());
+        let result = git_pull("missing", true);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+        let result = git_commit("missing", "message", true, false);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
     }
 
     #[test]
-    fn test_git_push() {
-        let _ = reset_shortcuts();
-        let _ = add_shortcut("proj1", vec![".".to_string()]);
-        let result = git_push("proj1", "Initial commit");
-        assert!(result.is_ok());
+    fn test_merge_shortcut_lists() {
+        let shortcut = |name: &str, command: &str| Shortcut {
+            project_name: name.to_string(),
+            run_command: vec![command.to_string()],
+            ..Default::default()
+        };
+        let global = vec![shortcut("build", "make"), shortcut("docs", "mdbook")];
+        let local = vec![shortcut("Build", "cargo"), shortcut("test", "cargo")];
+        let merged = merge_shortcut_lists(global, local);
+        let pairs: Vec<(&str, &str)> = merged
+            .iter()
+            .map(|s| (s.project_name.as_str(), s.run_command[0].as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("docs", "mdbook"), ("Build", "cargo"), ("test", "cargo")]
+        );
+    }
+
+    #[test]
+    fn test_find_local_config_from() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("home");
+        let project = home.join("project");
+        let nested = project.join("src").join("bin");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_local_config_from(&nested, Some(&home)), None);
+
+        fs::write(dir.path().join(LOCAL_CONFIG_FILE_NAME), "[]").unwrap();
+        // The search stops at the home directory
+        assert_eq!(find_local_config_from(&nested, Some(&home)), None);
+
+        let local = project.join(LOCAL_CONFIG_FILE_NAME);
+        fs::write(&local, "[]").unwrap();
+        assert_eq!(find_local_config_from(&nested, Some(&home)), Some(local));
     }
 }